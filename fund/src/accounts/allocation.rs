@@ -0,0 +1,50 @@
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use serum_common::pack::*;
+use solana_client_gen::prelude::*;
+
+/// Allocation lives at a program derived address of the fund and the depositor.
+/// It bounds what the depositor may put into the fund over all of their
+/// deposits, once a fund has allocations only depositors holding one can deposit.
+#[derive(Default, Debug, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct Allocation {
+    /// check to see if an allocation is initialized
+    pub initialized: bool,
+    /// fund the allocation is for
+    pub fund: Pubkey,
+    /// depositor authority the allocation is for
+    pub depositor: Pubkey,
+    /// least the depositor has to have deposited after a deposit
+    pub min: u64,
+    /// most the depositor can deposit in total
+    pub max: u64,
+    /// running total of the deposits
+    pub deposited: u64,
+}
+
+impl Allocation {
+    pub fn seeds<'a>(fund: &'a Pubkey, depositor: &'a Pubkey) -> [&'a [u8]; 3] {
+        [b"allocation", fund.as_ref(), depositor.as_ref()]
+    }
+    pub fn signer_seeds<'a>(
+        fund: &'a Pubkey,
+        depositor: &'a Pubkey,
+        nonce: &'a u8,
+    ) -> [&'a [u8]; 4] {
+        [
+            b"allocation",
+            fund.as_ref(),
+            depositor.as_ref(),
+            bytemuck::bytes_of(nonce),
+        ]
+    }
+
+    /// remaining returns how much the depositor can still deposit
+    pub fn remaining(&self) -> u64 {
+        self.max.saturating_sub(self.deposited)
+    }
+    pub fn add(&mut self, amount: u64) {
+        self.deposited += amount;
+    }
+}
+
+serum_common::packable!(Allocation);
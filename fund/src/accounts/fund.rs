@@ -1,7 +1,11 @@
+use crate::error::{FundError, FundErrorCode};
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use serum_common::pack::*;
 use solana_client_gen::prelude::*;
 
+/// Scale of the fixed-point payback per share values
+pub const PAYBACK_PRECISION: u128 = 1_000_000_000_000;
+
 #[derive(Debug, Clone, Copy, BorshSerialize, BorshDeserialize, BorshSchema, PartialEq)]
 pub enum FundType {
     /// similar to a gofundme
@@ -9,6 +13,13 @@ pub enum FundType {
     Raise {
         private: bool,
     },
+    /// all-or-nothing crowdfunding, depositors are refunded if the goal is
+    /// not raised by the deadline
+    Campaign {
+        goal: u64,
+        /// unix timestamp deposits are accepted until
+        deadline: i64,
+    },
 }
 
 impl Default for FundType {
@@ -17,6 +28,49 @@ impl Default for FundType {
     }
 }
 
+impl FundType {
+    /// mints_receipts is true for funds minting a receipt token on deposit
+    pub fn mints_receipts(&self) -> bool {
+        match self {
+            FundType::FundMe => false,
+            FundType::Raise { .. } | FundType::Campaign { .. } => true,
+        }
+    }
+}
+
+/// Governance holds the rules proposals are decided by. Fractions are given
+/// in basis points.
+#[derive(Default, Debug, Clone, Copy, BorshSerialize, BorshDeserialize, BorshSchema, PartialEq)]
+pub struct Governance {
+    /// minimum fraction of the fund shares that has to vote on a proposal
+    pub quorum: u16,
+    /// fraction of yes votes among yes and no votes a proposal has to exceed
+    pub threshold: u16,
+    /// seconds a proposal is open for voting
+    pub voting_period: i64,
+    /// seconds between a proposal succeeding and it being executable
+    pub execution_delay: i64,
+}
+
+/// Time after which the owner can take a paused fund back from its guardian
+pub const MAX_PAUSE: i64 = 14 * 24 * 60 * 60;
+
+/// Longest a proposal can be open for voting
+pub const MAX_VOTING_PERIOD: i64 = 365 * 24 * 60 * 60;
+/// Longest a succeeded proposal can wait to be executed
+pub const MAX_EXECUTION_DELAY: i64 = 365 * 24 * 60 * 60;
+
+impl Governance {
+    pub fn is_valid(&self) -> bool {
+        self.quorum <= 10_000
+            && self.threshold < 10_000
+            && self.voting_period > 0
+            && self.voting_period <= MAX_VOTING_PERIOD
+            && self.execution_delay >= 0
+            && self.execution_delay <= MAX_EXECUTION_DELAY
+    }
+}
+
 /// The Owner of the fund has the right to withdraw all or some of the funds
 #[derive(Default, Debug, BorshSerialize, BorshDeserialize, BorshSchema)]
 pub struct Fund {
@@ -43,7 +97,8 @@ pub struct Fund {
 
     /// Params
 
-    /// shares, shares increment with investment, but do not decrement with withdraw
+    /// shares, shares increment with investment, but do not decrement with withdraw.
+    /// They are burned when a depositor rage quits.
     pub shares: u64,
     /// nft account
     pub nft_account: Pubkey,
@@ -51,60 +106,157 @@ pub struct Fund {
     pub nft_mint: Pubkey,
     /// whitelist represents a list of pubkeys that can deposit into a fund
     pub whitelist: Pubkey,
+    /// rules for deciding proposals
+    pub governance: Governance,
 
     /// Payback info
-    pub paybacks: Vec<Payback>,
-    /// round refers to the round of payback
+
+    /// cumulative payback per share of all funded rounds, scaled by PAYBACK_PRECISION
+    pub payback_per_share: u128,
+    /// token account holding paybacks until they are claimed
+    pub payback_vault: Pubkey,
+    /// round refers to the round of payback, each round lives in its own account
     pub round: u32,
+    /// unix timestamp deposits open at, 0 when open from initialization
+    pub opens_at: i64,
+    /// unix timestamp deposits close at, 0 when the fund is only closed by its owner
+    pub closes_at: i64,
+    /// total sent out of the vault by withdrawals and executed proposals
+    pub withdrawn: u64,
+    /// owner the fund is handed over to once they accept, default when none
+    pub pending_owner: Pubkey,
+    /// key allowed to pause the fund in an emergency, default when none
+    pub guardian: Pubkey,
+    /// paused freezes everything but share holders leaving the fund
+    pub paused: bool,
+    /// unix timestamp the fund was paused at
+    pub paused_at: i64,
+    /// merkle root of the depositors allowed into a private raise, replaces the
+    /// whitelist pages while set
+    pub whitelist_root: [u8; 32],
+    /// allocations limits deposits to the depositors holding an allocation
+    pub allocations: bool,
 }
 
 impl Fund {
-    pub fn deduct(&mut self, amount: u64) {
-        if self.balance > 0 {
-            self.balance -= amount;
-        }
+    /// is_open is true while the fund is open and inside its deposit window
+    pub fn is_open(&self, now: i64) -> bool {
+        self.open
+            && (self.opens_at == 0 || now >= self.opens_at)
+            && (self.closes_at == 0 || now < self.closes_at)
+    }
+    pub fn deduct(&mut self, amount: u64) -> Result<(), FundError> {
+        self.balance = self
+            .balance
+            .checked_sub(amount)
+            .ok_or(FundErrorCode::InsufficientBalance)?;
+        self.withdrawn = self
+            .withdrawn
+            .checked_add(amount)
+            .ok_or(FundErrorCode::MathOverflow)?;
+        Ok(())
     }
     /// Add adds the depoist amount to the total balance and shares
     pub fn add(&mut self, amount: u64) {
         self.balance += amount;
-        if self.fund_type.eq(&FundType::Raise { private: true })
-            || self.fund_type.eq(&FundType::Raise { private: false })
-        {
+        if self.fund_type.mints_receipts() {
             self.shares += amount;
         }
     }
+    /// goal_reached is true once a campaign raised its goal. Receipts are only
+    /// burned by refunds, so the shares hold the total raised.
+    pub fn goal_reached(&self) -> bool {
+        match self.fund_type {
+            FundType::Campaign { goal, .. } => self.shares >= goal,
+            _ => true,
+        }
+    }
+    /// refund returns the deposit of burned campaign receipts
+    pub fn refund(&mut self, amount: u64) -> Result<(), FundError> {
+        self.shares = self
+            .shares
+            .checked_sub(amount)
+            .ok_or(FundErrorCode::InsufficientShares)?;
+        self.balance = self
+            .balance
+            .checked_sub(amount)
+            .ok_or(FundErrorCode::InsufficientBalance)?;
+        Ok(())
+    }
+    /// rage_quit removes the burned shares and the tokens paid out for them
+    pub fn rage_quit(&mut self, shares: u64, amount: u64) -> Result<(), FundError> {
+        self.shares = self
+            .shares
+            .checked_sub(shares)
+            .ok_or(FundErrorCode::InsufficientShares)?;
+        self.balance = self.balance.saturating_sub(amount);
+        Ok(())
+    }
     /// close_fund is called when the owner starts the withdrawl process
     pub fn close_fund(&mut self) {
         if self.open {
             self.open = false;
         }
     }
-
-    pub fn add_new_payback(&mut self, total: u64, per_share: u64) {
-        let pb = Payback::new(total, per_share);
-        self.paybacks.push(pb);
-        self.round += 1;
+    /// reopen opens a closed fund for deposits again, optionally raising the
+    /// max balance and moving the end of the deposit window
+    pub fn reopen(&mut self, max_balance: Option<u64>, closes_at: Option<i64>) {
+        self.open = true;
+        if let Some(max_balance) = max_balance {
+            self.max_balance = max_balance;
+        }
+        if let Some(closes_at) = closes_at {
+            self.closes_at = closes_at;
+        }
     }
-}
-
-serum_common::packable!(Fund);
-
-#[derive(Default, Debug, BorshSerialize, BorshDeserialize, BorshSchema)]
-pub struct Payback {
-    /// total of the paybck
-    pub total: u64,
-    /// per_share
-    pub per_share: u64,
-}
 
-impl Payback {
-    pub fn new(total: u64, per_share: u64) -> Self {
-        Payback { total, per_share }
+    /// propose_owner starts handing the fund over, proposing the default
+    /// pubkey cancels a pending transfer
+    pub fn propose_owner(&mut self, new_owner: Pubkey) {
+        self.pending_owner = new_owner;
     }
-    pub fn add_total(&mut self, amount: u64) {
-        self.total += amount;
+    /// accept_ownership completes the transfer to the pending owner
+    pub fn accept_ownership(&mut self) {
+        self.owner = self.pending_owner;
+        self.pending_owner = Pubkey::default();
     }
-    pub fn add_payback_per_share(&mut self, amount: u64) {
-        self.per_share += amount;
+    /// pause freezes the fund until the guardian unpauses it
+    pub fn pause(&mut self, now: i64) {
+        self.paused = true;
+        self.paused_at = now;
+    }
+    /// pause_expired is true once a pause lasted long enough for the owner to
+    /// replace the guardian
+    pub fn pause_expired(&self, now: i64) -> bool {
+        self.paused && now >= self.paused_at.saturating_add(MAX_PAUSE)
+    }
+    pub fn unpause(&mut self) {
+        self.paused = false;
+    }
+    /// set_private toggles a raise between private and public
+    pub fn set_private(&mut self, private: bool) {
+        if let FundType::Raise { .. } = self.fund_type {
+            self.fund_type = FundType::Raise { private };
+        }
+    }
+
+    /// add_new_payback starts the next round of payback
+    pub fn add_new_payback(&mut self) {
+        self.round += 1;
+    }
+    /// fund_payback is called once the total of a round is deposited, spreading
+    /// it over the outstanding shares. Returns the payback per share of the round.
+    pub fn fund_payback(&mut self, total: u64) -> Result<u128, FundError> {
+        let per_share = (total as u128)
+            .checked_mul(PAYBACK_PRECISION)
+            .and_then(|scaled| scaled.checked_div(self.shares as u128))
+            .ok_or(FundErrorCode::MathOverflow)?;
+        self.payback_per_share = self
+            .payback_per_share
+            .checked_add(per_share)
+            .ok_or(FundErrorCode::MathOverflow)?;
+        Ok(per_share)
     }
 }
+
+serum_common::packable!(Fund);
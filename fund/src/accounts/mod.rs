@@ -1,7 +1,19 @@
+pub mod allocation;
 pub mod fund;
+pub mod payback;
+pub mod payback_claim;
+pub mod position;
+pub mod proposal;
 pub mod vault;
+pub mod vote_record;
 pub mod whitelist;
 
+pub use allocation::Allocation;
 pub use fund::Fund;
 pub use fund::FundType;
+pub use payback::Payback;
+pub use payback_claim::PaybackClaim;
+pub use position::Position;
+pub use proposal::Proposal;
+pub use vote_record::{Vote, VoteRecord};
 pub use whitelist::Whitelist;
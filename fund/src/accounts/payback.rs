@@ -0,0 +1,48 @@
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use serum_common::pack::*;
+use solana_client_gen::prelude::*;
+
+/// Payback is a single round of payback, living at a program derived address
+/// of the fund and the round.
+#[derive(Default, Debug, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct Payback {
+    /// check to see if a payback is initialized
+    pub initialized: bool,
+    /// fund paying back
+    pub fund: Pubkey,
+    /// round of the payback
+    pub round: u32,
+    /// total of the paybck
+    pub total: u64,
+    /// payback per share of the round, scaled by PAYBACK_PRECISION
+    pub per_share: u128,
+    /// funded is set once the total is deposited into the payback vault
+    pub funded: bool,
+}
+
+impl Payback {
+    pub fn seeds<'a>(fund: &'a Pubkey, round: &'a u32) -> [&'a [u8]; 3] {
+        [b"round", fund.as_ref(), bytemuck::bytes_of(round)]
+    }
+    pub fn signer_seeds<'a>(fund: &'a Pubkey, round: &'a u32, nonce: &'a u8) -> [&'a [u8]; 4] {
+        [
+            b"round",
+            fund.as_ref(),
+            bytemuck::bytes_of(round),
+            bytemuck::bytes_of(nonce),
+        ]
+    }
+    pub fn add_total(&mut self, amount: u64) {
+        self.total += amount;
+    }
+    pub fn add_payback_per_share(&mut self, amount: u128) {
+        self.per_share += amount;
+    }
+    /// mark_funded is called once the total is deposited into the payback vault
+    pub fn mark_funded(&mut self, per_share: u128) {
+        self.per_share = per_share;
+        self.funded = true;
+    }
+}
+
+serum_common::packable!(Payback);
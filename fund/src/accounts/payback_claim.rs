@@ -0,0 +1,190 @@
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use serum_common::pack::*;
+use solana_client_gen::prelude::*;
+
+use super::fund::PAYBACK_PRECISION;
+use crate::error::{FundError, FundErrorCode};
+use std::convert::TryFrom;
+
+/// PaybackClaim lives at a program derived address of the fund and the share
+/// holder. It snapshots the payback per share of the fund whenever the shares
+/// of the holder change, so every round is paid out on the shares held while it
+/// was funded. The shares also weigh the votes of the holder, so it keeps
+/// count of the votes still open to correct their tally when shares are burned.
+#[derive(Default, Debug, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct PaybackClaim {
+    /// check to see if a claim is initialized
+    pub initialized: bool,
+    /// fund paying back
+    pub fund: Pubkey,
+    /// share holder authority
+    pub holder: Pubkey,
+    /// shares paybacks are earned on
+    pub shares: u64,
+    /// shares * payback per share at the last snapshot, scaled by PAYBACK_PRECISION
+    pub debt: u128,
+    /// paybacks earned but not yet claimed, scaled by PAYBACK_PRECISION
+    pub owed: u128,
+    /// total amount claimed
+    pub claimed: u64,
+    /// votes cast since votes_since, while any of them is still open
+    pub open_votes: u32,
+    /// unix timestamp the open votes are counted from
+    pub votes_since: i64,
+    /// unix timestamp the last open vote ends at
+    pub voting_until: i64,
+}
+
+impl PaybackClaim {
+    pub fn seeds<'a>(fund: &'a Pubkey, holder: &'a Pubkey) -> [&'a [u8]; 3] {
+        [b"payback", fund.as_ref(), holder.as_ref()]
+    }
+    pub fn signer_seeds<'a>(fund: &'a Pubkey, holder: &'a Pubkey, nonce: &'a u8) -> [&'a [u8]; 4] {
+        [
+            b"payback",
+            fund.as_ref(),
+            holder.as_ref(),
+            bytemuck::bytes_of(nonce),
+        ]
+    }
+
+    /// settle moves everything earned since the last snapshot into owed
+    fn settle(&mut self, payback_per_share: u128) -> Result<(), FundError> {
+        let accrued = debt(self.shares, payback_per_share)?;
+        let earned = accrued
+            .checked_sub(self.debt)
+            .ok_or(FundErrorCode::MathOverflow)?;
+        self.owed = self
+            .owed
+            .checked_add(earned)
+            .ok_or(FundErrorCode::MathOverflow)?;
+        self.debt = accrued;
+        Ok(())
+    }
+    pub fn add_shares(&mut self, shares: u64, payback_per_share: u128) -> Result<(), FundError> {
+        self.settle(payback_per_share)?;
+        self.shares = self
+            .shares
+            .checked_add(shares)
+            .ok_or(FundErrorCode::MathOverflow)?;
+        self.debt = debt(self.shares, payback_per_share)?;
+        Ok(())
+    }
+    pub fn remove_shares(&mut self, shares: u64, payback_per_share: u128) -> Result<(), FundError> {
+        self.settle(payback_per_share)?;
+        self.shares = self
+            .shares
+            .checked_sub(shares)
+            .ok_or(FundErrorCode::InsufficientShares)?;
+        self.debt = debt(self.shares, payback_per_share)?;
+        Ok(())
+    }
+    /// claim returns the amount to pay out, keeping the fraction that does
+    /// not add up to a whole token owed
+    pub fn claim(&mut self, payback_per_share: u128) -> Result<u64, FundError> {
+        self.settle(payback_per_share)?;
+        let amount = self.owed / PAYBACK_PRECISION;
+        let amount = u64::try_from(amount).map_err(|_| FundErrorCode::MathOverflow)?;
+        self.owed -= amount as u128 * PAYBACK_PRECISION;
+        self.claimed = self
+            .claimed
+            .checked_add(amount)
+            .ok_or(FundErrorCode::MathOverflow)?;
+        Ok(amount)
+    }
+    /// cast_vote counts a vote on a proposal voting until the given timestamp,
+    /// starting the count over once all earlier votes ended
+    pub fn cast_vote(&mut self, voting_ends_at: i64, now: i64) {
+        if !self.voting(now) {
+            self.open_votes = 0;
+            self.votes_since = now;
+        }
+        self.open_votes += 1;
+        self.voting_until = std::cmp::max(self.voting_until, voting_ends_at);
+    }
+    /// voting is true while a vote of the holder is still open
+    pub fn voting(&self, now: i64) -> bool {
+        now < self.voting_until
+    }
+}
+
+/// debt returns the paybacks earned by shares at the given payback per share
+fn debt(shares: u64, payback_per_share: u128) -> Result<u128, FundError> {
+    (shares as u128)
+        .checked_mul(payback_per_share)
+        .ok_or_else(|| FundErrorCode::MathOverflow.into())
+}
+
+serum_common::packable!(PaybackClaim);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accounts::fund::Fund;
+
+    fn fund(shares: u64) -> Fund {
+        Fund {
+            shares,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn claims_share_of_round() {
+        let mut fund = fund(100);
+        let mut a = PaybackClaim::default();
+        let mut b = PaybackClaim::default();
+        a.add_shares(25, fund.payback_per_share).unwrap();
+        b.add_shares(75, fund.payback_per_share).unwrap();
+
+        fund.fund_payback(1_000);
+
+        assert_eq!(a.claim(fund.payback_per_share).unwrap(), 250);
+        assert_eq!(b.claim(fund.payback_per_share).unwrap(), 750);
+        assert_eq!(a.claim(fund.payback_per_share).unwrap(), 0);
+    }
+
+    #[test]
+    fn new_shares_skip_funded_rounds() {
+        let mut fund = fund(100);
+        let mut early = PaybackClaim::default();
+        early.add_shares(100, fund.payback_per_share).unwrap();
+        fund.fund_payback(500).unwrap();
+
+        let mut late = PaybackClaim::default();
+        late.add_shares(100, fund.payback_per_share).unwrap();
+        fund.shares += 100;
+        fund.fund_payback(200).unwrap();
+
+        assert_eq!(early.claim(fund.payback_per_share).unwrap(), 600);
+        assert_eq!(late.claim(fund.payback_per_share).unwrap(), 100);
+    }
+
+    #[test]
+    fn removed_shares_keep_earned_paybacks() {
+        let mut fund = fund(10);
+        let mut claim = PaybackClaim::default();
+        claim.add_shares(10, fund.payback_per_share).unwrap();
+        fund.fund_payback(100).unwrap();
+
+        claim.remove_shares(10, fund.payback_per_share).unwrap();
+        fund.shares = 10;
+        fund.fund_payback(100).unwrap();
+
+        assert_eq!(claim.claim(fund.payback_per_share).unwrap(), 100);
+    }
+
+    #[test]
+    fn keeps_fractions_owed() {
+        let mut fund = fund(2);
+        let mut claim = PaybackClaim::default();
+        claim.add_shares(1, fund.payback_per_share).unwrap();
+
+        fund.fund_payback(1).unwrap();
+        assert_eq!(claim.claim(fund.payback_per_share).unwrap(), 0);
+
+        fund.fund_payback(1).unwrap();
+        assert_eq!(claim.claim(fund.payback_per_share).unwrap(), 1);
+        assert_eq!(claim.claimed, 1);
+    }
+}
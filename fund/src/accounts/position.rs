@@ -0,0 +1,90 @@
+use crate::error::{FundError, FundErrorCode};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use serum_common::pack::*;
+use solana_client_gen::prelude::*;
+
+/// Position lives at a program derived address of the fund and the depositor.
+/// It is created on the first deposit and keeps the lifetime totals of the
+/// depositor in the fund.
+#[derive(Default, Debug, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct Position {
+    /// check to see if a position is initialized
+    pub initialized: bool,
+    /// fund the position is in
+    pub fund: Pubkey,
+    /// depositor authority owning the position
+    pub depositor: Pubkey,
+    /// total amount deposited
+    pub deposited: u64,
+    /// shares received for the deposits and still held
+    pub shares: u64,
+    /// total vault tokens paid out for rage quit shares
+    pub withdrawn: u64,
+    /// total campaign deposits refunded
+    pub refunded: u64,
+    /// total amount of paybacks claimed
+    pub paybacks_claimed: u64,
+    /// unix timestamp of the last deposit
+    pub last_deposit_at: i64,
+}
+
+impl Position {
+    pub fn seeds<'a>(fund: &'a Pubkey, depositor: &'a Pubkey) -> [&'a [u8]; 3] {
+        [b"position", fund.as_ref(), depositor.as_ref()]
+    }
+    pub fn signer_seeds<'a>(
+        fund: &'a Pubkey,
+        depositor: &'a Pubkey,
+        nonce: &'a u8,
+    ) -> [&'a [u8]; 4] {
+        [
+            b"position",
+            fund.as_ref(),
+            depositor.as_ref(),
+            bytemuck::bytes_of(nonce),
+        ]
+    }
+
+    /// open initializes the position. Holders of a raise from before positions
+    /// existed bring the shares on their payback claim, which are counted as
+    /// deposited since shares are minted one to one with deposits.
+    pub fn open(&mut self, fund: Pubkey, depositor: Pubkey, held: u64) {
+        self.initialized = true;
+        self.fund = fund;
+        self.depositor = depositor;
+        self.deposited = held;
+        self.shares = held;
+    }
+    pub fn deposit(&mut self, amount: u64, shares: u64, now: i64) {
+        self.deposited += amount;
+        self.shares += shares;
+        self.last_deposit_at = now;
+    }
+    /// rage_quit records the vault tokens paid out for burned shares
+    pub fn rage_quit(&mut self, shares: u64, amount: u64) -> Result<(), FundError> {
+        self.shares = self
+            .shares
+            .checked_sub(shares)
+            .ok_or(FundErrorCode::InsufficientShares)?;
+        self.withdrawn = self
+            .withdrawn
+            .checked_add(amount)
+            .ok_or(FundErrorCode::MathOverflow)?;
+        Ok(())
+    }
+    /// refund records a refunded campaign deposit. Campaign receipts can be
+    /// transferred, so the receipts refunded can exceed the shares deposited.
+    pub fn refund(&mut self, amount: u64) -> Result<(), FundError> {
+        self.shares = self.shares.saturating_sub(amount);
+        self.refunded = self
+            .refunded
+            .checked_add(amount)
+            .ok_or(FundErrorCode::MathOverflow)?;
+        Ok(())
+    }
+    pub fn claim(&mut self, amount: u64) {
+        self.paybacks_claimed += amount;
+    }
+}
+
+serum_common::packable!(Position);
@@ -0,0 +1,128 @@
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use serum_common::pack::*;
+use solana_client_gen::prelude::*;
+
+use super::{fund::Governance, vote_record::Vote};
+use crate::error::{FundError, FundErrorCode};
+
+#[derive(Debug, Clone, Copy, BorshSerialize, BorshDeserialize, BorshSchema, PartialEq)]
+pub enum ProposalState {
+    /// share holders can vote on the proposal
+    Active,
+    /// the vote met quorum and approval threshold
+    Succeeded,
+    /// the vote missed quorum or approval threshold
+    Defeated,
+    /// the proposer withdrew the proposal
+    Cancelled,
+    /// the funds were sent to the recipient
+    Executed,
+}
+
+impl Default for ProposalState {
+    fn default() -> Self {
+        ProposalState::Active
+    }
+}
+
+/// A Proposal asks the share holders of a fund to send tokens out of the vault
+#[derive(Default, Debug, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct Proposal {
+    /// check to see if a proposal is initialized
+    pub initialized: bool,
+    /// state of the proposal
+    pub state: ProposalState,
+    /// fund the proposal belongs to
+    pub fund: Pubkey,
+    /// share holder that created the proposal
+    pub proposer: Pubkey,
+    /// token account receiving the funds
+    pub recipient: Pubkey,
+    /// amount to send from the vault
+    pub amount: u64,
+    /// hash of the off-chain description
+    pub description_hash: [u8; 32],
+    /// unix timestamp after which no more votes are accepted
+    pub voting_ends_at: i64,
+    /// unix timestamp the vote was finalized at
+    pub finalized_at: i64,
+    /// outstanding shares of the fund when the proposal was created, the
+    /// quorum is measured against them
+    pub shares: u64,
+
+    /// Tally
+
+    /// shares voting for the proposal
+    pub yes_votes: u64,
+    /// shares voting against the proposal
+    pub no_votes: u64,
+    /// shares abstaining
+    pub abstain_votes: u64,
+}
+
+impl Proposal {
+    pub fn is_active(&self) -> bool {
+        self.state == ProposalState::Active
+    }
+    /// tally adds the weight of a vote to the proposal
+    pub fn tally(&mut self, vote: Vote, weight: u64) -> Result<(), FundError> {
+        let votes = self.votes_mut(vote);
+        *votes = votes
+            .checked_add(weight)
+            .ok_or(FundErrorCode::MathOverflow)?;
+        Ok(())
+    }
+    /// untally takes weight off a vote, when the shares backing it are burned
+    pub fn untally(&mut self, vote: Vote, weight: u64) -> Result<(), FundError> {
+        let votes = self.votes_mut(vote);
+        *votes = votes
+            .checked_sub(weight)
+            .ok_or(FundErrorCode::InvalidVoteRecord)?;
+        Ok(())
+    }
+    fn votes_mut(&mut self, vote: Vote) -> &mut u64 {
+        match vote {
+            Vote::Yes => &mut self.yes_votes,
+            Vote::No => &mut self.no_votes,
+            Vote::Abstain => &mut self.abstain_votes,
+        }
+    }
+    /// passed checks the tally against the quorum and approval threshold
+    /// of the fund
+    pub fn passed(&self, governance: &Governance) -> bool {
+        let yes = self.yes_votes as u128;
+        let no = self.no_votes as u128;
+        let turnout = yes + no + self.abstain_votes as u128;
+
+        let quorum = turnout * 10_000 >= governance.quorum as u128 * self.shares as u128;
+        let approved = yes * 10_000 > governance.threshold as u128 * (yes + no);
+
+        quorum && approved
+    }
+    /// finalize closes voting and records the outcome
+    pub fn finalize(&mut self, governance: &Governance, now: i64) {
+        self.state = if self.passed(governance) {
+            ProposalState::Succeeded
+        } else {
+            ProposalState::Defeated
+        };
+        self.finalized_at = now;
+    }
+    /// executable_at returns the unix timestamp a succeeded proposal can be
+    /// executed from
+    pub fn executable_at(&self, governance: &Governance) -> Result<i64, FundError> {
+        self.finalized_at
+            .checked_add(governance.execution_delay)
+            .ok_or_else(|| FundErrorCode::MathOverflow.into())
+    }
+    /// execute is called once the funds are sent to the recipient
+    pub fn execute(&mut self) {
+        self.state = ProposalState::Executed;
+    }
+    /// cancel is called when the proposer withdraws the proposal
+    pub fn cancel(&mut self) {
+        self.state = ProposalState::Cancelled;
+    }
+}
+
+serum_common::packable!(Proposal);
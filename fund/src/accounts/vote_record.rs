@@ -0,0 +1,54 @@
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use serum_common::pack::*;
+use solana_client_gen::prelude::*;
+
+#[derive(Debug, Clone, Copy, BorshSerialize, BorshDeserialize, BorshSchema, PartialEq)]
+pub enum Vote {
+    Yes,
+    No,
+    Abstain,
+}
+
+impl Default for Vote {
+    fn default() -> Self {
+        Vote::Abstain
+    }
+}
+
+/// VoteRecord lives at a program derived address of the proposal and the voter,
+/// its existence prevents a share holder from voting twice on a proposal.
+#[derive(Default, Debug, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub struct VoteRecord {
+    /// check to see if a vote record is initialized
+    pub initialized: bool,
+    /// proposal voted on
+    pub proposal: Pubkey,
+    /// voter authority
+    pub voter: Pubkey,
+    /// vote cast
+    pub vote: Vote,
+    /// shares backing the vote
+    pub weight: u64,
+    /// unix timestamp the vote was cast at
+    pub cast_at: i64,
+}
+
+impl VoteRecord {
+    pub fn seeds<'a>(proposal: &'a Pubkey, voter: &'a Pubkey) -> [&'a [u8]; 3] {
+        [b"vote", proposal.as_ref(), voter.as_ref()]
+    }
+    pub fn signer_seeds<'a>(
+        proposal: &'a Pubkey,
+        voter: &'a Pubkey,
+        nonce: &'a u8,
+    ) -> [&'a [u8]; 4] {
+        [
+            b"vote",
+            proposal.as_ref(),
+            voter.as_ref(),
+            bytemuck::bytes_of(nonce),
+        ]
+    }
+}
+
+serum_common::packable!(VoteRecord);
@@ -7,6 +7,12 @@ use solana_client_gen::solana_sdk::pubkey::Pubkey;
 /// SRM program is allowed to delegate funds to. This is used, for example,
 /// to allow locked SRM to be sent to the staking program.
 ///
+/// A whitelist is a chain of pages. Every page starts with a header holding
+/// the fund it belongs to, the next page of the chain, the number of entries
+/// and the capacity of the page, followed by the entries. The capacity is
+/// derived from the size of the account, so pages can be of any size and a
+/// full whitelist grows by chaining another page.
+///
 /// Note that the whitelist backing storage is too large to be able to pack/unpack
 /// it on the BPF stack. As a result, we just wrap the raw data array
 /// and access the data as needed with the api accessors provided here.
@@ -22,36 +28,101 @@ pub struct Whitelist<'a> {
 impl<'a> Whitelist<'a> {
     /// Byte size for a single item in the whitelist.
     pub const ITEM_SIZE: usize = 32;
-    /// Number of items in the whitelist.
+    /// Byte size of the page header: fund, next page, count and capacity.
+    pub const HEADER_SIZE: usize = 32 + 32 + 4 + 4;
+    /// Number of items in a default sized page.
     pub const LEN: usize = 50;
-    /// Byte size of the entire whitelist.
-    pub const SIZE: usize = 32 * Whitelist::LEN;
+    /// Byte size of a default sized page.
+    pub const SIZE: usize = Whitelist::HEADER_SIZE + Whitelist::ITEM_SIZE * Whitelist::LEN;
+
+    const NEXT_OFFSET: usize = 32;
+    const COUNT_OFFSET: usize = 64;
+    const CAPACITY_OFFSET: usize = 68;
 
     pub fn new(acc_info: AccountInfo<'a>) -> Result<Self, FundError> {
-        if acc_info.try_data_len()? != Whitelist::SIZE {
+        let len = acc_info.try_data_len()?;
+        if len < Whitelist::HEADER_SIZE + Whitelist::ITEM_SIZE
+            || (len - Whitelist::HEADER_SIZE) % Whitelist::ITEM_SIZE != 0
+        {
             return Err(FundErrorCode::WhitelistInvalidData.into());
         }
         Ok(Self { acc_info })
     }
 
+    /// Writes the header of a new page belonging to the given fund.
+    pub fn init(&self, fund: &Pubkey) -> Result<(), FundError> {
+        if self.fund()? != Pubkey::default() {
+            return Err(FundErrorCode::AlreadyInitialized.into());
+        }
+        let capacity =
+            (self.acc_info.try_data_len()? - Whitelist::HEADER_SIZE) / Whitelist::ITEM_SIZE;
+
+        let mut data = self.acc_info.try_borrow_mut_data()?;
+        array_mut_ref![data, 0, 32].copy_from_slice(fund.as_ref());
+        array_mut_ref![data, Whitelist::CAPACITY_OFFSET, 4]
+            .copy_from_slice(&(capacity as u32).to_le_bytes());
+
+        Ok(())
+    }
+
+    /// Returns the fund the page belongs to.
+    pub fn fund(&self) -> Result<Pubkey, FundError> {
+        let data = self.acc_info.try_borrow_data()?;
+        Ok(Pubkey::new(array_ref![data, 0, 32]))
+    }
+
+    /// Returns the next page of the chain, the default pubkey on the last page.
+    pub fn next(&self) -> Result<Pubkey, FundError> {
+        let data = self.acc_info.try_borrow_data()?;
+        Ok(Pubkey::new(array_ref![data, Whitelist::NEXT_OFFSET, 32]))
+    }
+
+    /// Chains the given page after this one.
+    pub fn set_next(&self, next: &Pubkey) -> Result<(), FundError> {
+        let mut data = self.acc_info.try_borrow_mut_data()?;
+        array_mut_ref![data, Whitelist::NEXT_OFFSET, 32].copy_from_slice(next.as_ref());
+        Ok(())
+    }
+
+    /// Returns the number of entries in the page.
+    pub fn count(&self) -> Result<usize, FundError> {
+        let data = self.acc_info.try_borrow_data()?;
+        Ok(u32::from_le_bytes(*array_ref![data, Whitelist::COUNT_OFFSET, 4]) as usize)
+    }
+
+    fn set_count(&self, count: usize) -> Result<(), FundError> {
+        let mut data = self.acc_info.try_borrow_mut_data()?;
+        array_mut_ref![data, Whitelist::COUNT_OFFSET, 4]
+            .copy_from_slice(&(count as u32).to_le_bytes());
+        Ok(())
+    }
+
+    /// Returns the number of entries the page can hold.
+    pub fn capacity(&self) -> Result<usize, FundError> {
+        let data = self.acc_info.try_borrow_data()?;
+        Ok(u32::from_le_bytes(*array_ref![data, Whitelist::CAPACITY_OFFSET, 4]) as usize)
+    }
+
     /// Returns the PubKey at the given index.
     pub fn get_at(&self, index: usize) -> Result<Pubkey, FundError> {
         let data = self.acc_info.try_borrow_data()?;
-        let key = array_ref![data, index * Whitelist::ITEM_SIZE, Whitelist::ITEM_SIZE];
+        let offset = Whitelist::HEADER_SIZE + index * Whitelist::ITEM_SIZE;
+        let key = array_ref![data, offset, Whitelist::ITEM_SIZE];
         Ok(Pubkey::new(key))
     }
 
     /// Inserts the given PubKey at the given index.
     pub fn add_at(&self, index: usize, item: Pubkey) -> Result<(), FundError> {
         let mut data = self.acc_info.try_borrow_mut_data()?;
-        let dst = array_mut_ref![data, index * Whitelist::ITEM_SIZE, Whitelist::ITEM_SIZE];
+        let offset = Whitelist::HEADER_SIZE + index * Whitelist::ITEM_SIZE;
+        let dst = array_mut_ref![data, offset, Whitelist::ITEM_SIZE];
         dst.copy_from_slice(item.as_ref());
 
         Ok(())
     }
 
     /// Inserts the given PubKey at the first available index.
-    /// Returns Some(index) where the entry was inserted. If the Whitelist
+    /// Returns Some(index) where the entry was inserted. If the page
     /// is full, returns None.
     pub fn push(&self, entry: Pubkey) -> Result<Option<usize>, FundError> {
         let existing_idx = self.index_of(&entry)?;
@@ -62,30 +133,135 @@ impl<'a> Whitelist<'a> {
         let idx = self.index_of(&pk)?;
         if let Some(idx) = idx {
             self.add_at(idx, entry)?;
+            self.set_count(self.count()? + 1)?;
             return Ok(Some(idx));
         }
         Ok(idx)
     }
 
-    /// Deletes the given entry from the Whitelist.
+    /// Deletes the given entry from the page.
     pub fn delete(&self, entry: Pubkey) -> Result<Option<usize>, FundError> {
         let idx = self.index_of(&entry)?;
         if let Some(idx) = idx {
             let pk = Pubkey::new_from_array([0; 32]);
             self.add_at(idx, pk)?;
+            self.set_count(self.count()?.saturating_sub(1))?;
             return Ok(Some(idx));
         }
         Ok(idx)
     }
 
     pub fn index_of(&self, e: &Pubkey) -> Result<Option<usize>, FundError> {
-        for k in (0..Whitelist::SIZE).step_by(Whitelist::ITEM_SIZE) {
-            let curr_idx = k / Whitelist::ITEM_SIZE;
-            let entry = &self.get_at(curr_idx)?;
+        for idx in 0..self.capacity()? {
+            let entry = &self.get_at(idx)?;
             if entry == e {
-                return Ok(Some(k));
+                return Ok(Some(idx));
             }
         }
         Ok(None)
     }
+
+    /// Returns the page and index of the entry within a chain of pages.
+    pub fn find(pages: &[Whitelist], e: &Pubkey) -> Result<Option<(usize, usize)>, FundError> {
+        for (page, wl) in pages.iter().enumerate() {
+            if let Some(idx) = wl.index_of(e)? {
+                return Ok(Some((page, idx)));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(byte: u8) -> Pubkey {
+        Pubkey::new_from_array([byte; 32])
+    }
+
+    fn page_data(len: usize) -> Vec<u8> {
+        vec![0; Whitelist::HEADER_SIZE + len * Whitelist::ITEM_SIZE]
+    }
+
+    fn account<'a>(key: &'a Pubkey, lamports: &'a mut u64, data: &'a mut [u8]) -> AccountInfo<'a> {
+        AccountInfo::new(key, false, true, lamports, data, key, false, 0)
+    }
+
+    #[test]
+    fn init_writes_header() {
+        let (acc, mut lamports, mut data) = (key(0xaa), 0, page_data(3));
+        let wl = Whitelist::new(account(&acc, &mut lamports, &mut data)).unwrap();
+
+        wl.init(&key(1)).unwrap();
+        assert_eq!(wl.fund().unwrap(), key(1));
+        assert_eq!(wl.next().unwrap(), Pubkey::default());
+        assert_eq!(wl.count().unwrap(), 0);
+        assert_eq!(wl.capacity().unwrap(), 3);
+        assert!(wl.init(&key(1)).is_err());
+    }
+
+    #[test]
+    fn push_and_delete_track_count() {
+        let (acc, mut lamports, mut data) = (key(0xaa), 0, page_data(3));
+        let wl = Whitelist::new(account(&acc, &mut lamports, &mut data)).unwrap();
+        wl.init(&key(1)).unwrap();
+
+        assert_eq!(wl.push(key(2)).unwrap(), Some(0));
+        assert_eq!(wl.push(key(3)).unwrap(), Some(1));
+        assert!(wl.push(key(2)).is_err());
+        assert_eq!(wl.count().unwrap(), 2);
+        assert_eq!(wl.index_of(&key(3)).unwrap(), Some(1));
+
+        assert_eq!(wl.delete(key(2)).unwrap(), Some(0));
+        assert_eq!(wl.delete(key(2)).unwrap(), None);
+        assert_eq!(wl.count().unwrap(), 1);
+        assert_eq!(wl.push(key(4)).unwrap(), Some(0));
+    }
+
+    #[test]
+    fn push_on_full_page() {
+        let (acc, mut lamports, mut data) = (key(0xaa), 0, page_data(1));
+        let wl = Whitelist::new(account(&acc, &mut lamports, &mut data)).unwrap();
+        wl.init(&key(1)).unwrap();
+
+        assert_eq!(wl.push(key(2)).unwrap(), Some(0));
+        assert_eq!(wl.push(key(3)).unwrap(), None);
+        assert_eq!(wl.count().unwrap(), 1);
+    }
+
+    #[test]
+    fn find_across_pages() {
+        let (first_acc, mut first_lamports, mut first_data) = (key(0xaa), 0, page_data(1));
+        let (second_acc, mut second_lamports, mut second_data) = (key(0xbb), 0, page_data(1));
+        let first =
+            Whitelist::new(account(&first_acc, &mut first_lamports, &mut first_data)).unwrap();
+        let second =
+            Whitelist::new(account(&second_acc, &mut second_lamports, &mut second_data)).unwrap();
+        first.init(&key(1)).unwrap();
+        second.init(&key(1)).unwrap();
+        first.set_next(&second_acc).unwrap();
+        first.push(key(2)).unwrap();
+        second.push(key(3)).unwrap();
+
+        let pages = vec![first, second];
+        assert_eq!(pages[0].next().unwrap(), second_acc);
+        assert_eq!(Whitelist::find(&pages, &key(2)).unwrap(), Some((0, 0)));
+        assert_eq!(Whitelist::find(&pages, &key(3)).unwrap(), Some((1, 0)));
+        assert_eq!(Whitelist::find(&pages, &key(4)).unwrap(), None);
+    }
+
+    #[test]
+    fn rejects_headerless_whitelist() {
+        let len = Whitelist::ITEM_SIZE * Whitelist::LEN;
+        let (acc, mut lamports, mut data) = (key(0xaa), 0, vec![0; len]);
+        assert!(Whitelist::new(account(&acc, &mut lamports, &mut data)).is_err());
+    }
+
+    #[test]
+    fn rejects_partial_entries() {
+        let (acc, mut lamports, mut data) = (key(0xaa), 0, page_data(1));
+        data.push(0);
+        assert!(Whitelist::new(account(&acc, &mut lamports, &mut data)).is_err());
+    }
 }
@@ -43,6 +43,52 @@ pub enum FundErrorCode {
     WithdrawlSizeOverflow = 27,
     InvalidFund = 28,
     InsufficientBalance = 29,
+    InvalidProposal = 30,
+    ProposalNotActive = 31,
+    NotShareHolder = 32,
+    VoteAlreadyCast = 33,
+    InvalidVoteRecord = 34,
+    ProposalNotPassed = 35,
+    InvalidRecipient = 36,
+    WithdrawRequiresProposal = 37,
+    InvalidGovernance = 38,
+    InvalidClockSysvar = 39,
+    VotingInProgress = 40,
+    VotingEnded = 41,
+    ProposalTimelocked = 42,
+    InsufficientShares = 43,
+    InvalidPaybackVault = 44,
+    InvalidPaybackRound = 45,
+    PaybackAlreadyFunded = 46,
+    InvalidPaybackClaim = 49,
+    NoShares = 50,
+    InvalidCampaign = 51,
+    CampaignEnded = 52,
+    CampaignActive = 53,
+    GoalNotReached = 54,
+    GoalReached = 55,
+    FundNotYetOpen = 56,
+    DepositWindowClosed = 57,
+    InvalidDepositWindow = 58,
+    FundWithdrawn = 59,
+    InvalidMaxBalance = 60,
+    InvalidPendingOwner = 61,
+    InvalidAuthority = 62,
+    NotEnoughSigners = 63,
+    WithdrawUnauthorized = 64,
+    InvalidWithdrawDestination = 65,
+    InvalidWithdrawMint = 66,
+    FundPaused = 67,
+    InvalidGuardian = 68,
+    InvalidWhitelistProof = 69,
+    InvalidAllocation = 70,
+    AllocationExceeded = 71,
+    BelowMinimumContribution = 72,
+    InvalidPosition = 73,
+    MissingVoteRecords = 74,
+    InvalidWhitelistEntry = 75,
+    InvalidFreezeAuthority = 76,
+    MathOverflow = 77,
 }
 
 impl std::fmt::Display for FundErrorCode {
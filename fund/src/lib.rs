@@ -20,60 +20,312 @@ pub mod instruction {
         /// 2. `[]`         Mint
         /// 3. `[]`         Rent sysvar
         /// 4. `[writable]` Whitelist to initialize.
-        /// 5. `[writable]` Token mint representing the investment receipt.
+        /// 5. `[writable]` Token mint representing the investment receipt. For
+        ///                 raises the Tokenvault authority has to be its freeze authority.
         /// 6. `[writable]` Token account associated with the mint.
+        /// 7. `[]`         Token account holding paybacks, owned by the Tokenvault authority.
         Initialize {
-            /// Owner of the Fund
+            /// Owner of the Fund, a key or an spl token multisig
             owner: Pubkey,
-            /// Authority of the Fund
+            /// Authority of the Fund, runs day-to-day operations next to the owner
             authority: Pubkey,
             /// Max Size of a fund
             max_balance: u64,
             /// fund type
             fund_type: accounts::fund::FundType,
+            /// rules for deciding proposals of raise funds, voting and delaying
+            /// execution at most a year each
+            governance: accounts::fund::Governance,
+            /// unix timestamp deposits open at, open right away if none
+            opens_at: Option<i64>,
+            /// unix timestamp deposits close at, closed by the owner if none
+            closes_at: Option<i64>,
+            /// key allowed to pause the fund, only the guardian can replace it
+            guardian: Option<Pubkey>,
         },
-        /// Deposit sends tokens to a fund.
+        /// Deposit sends tokens to a fund. Only the amount left until the max
+        /// balance is taken, the fund closes once it is full. Funds with
+        /// allocations also cap the amount to what is left of the allocation.
         ///
         /// 0. `[writable]` Tokenvault
         /// 1. `[writable]` Depositor token account
-        /// 2. `[signer]`   Depositor authority
-        /// 3. `[]`         Fund
+        /// 2. `[writable, signer]` Depositor authority
+        /// 3. `[writable]` Fund
         /// 4. `[]`         Tokenvault Authority
         /// 5. `[]`         SPL token program
-        /// 6. `[writable]` Token mint representing the investment receipt.
-        /// 7  `[writable]` Token account associated with the mint.
-        /// 8. `[]`         Whitelist
-        Deposit { amount: u64 },
-        /// Withdraw funds from program account.
+        /// 6. `[]`         Clock sysvar, required for every fund type
+        /// 7. `[writable]` Position, program derived from the fund and depositor authority
+        /// 8. `[]`         System program
+        /// 9. `[]`         Rent sysvar
+        ///
+        /// Optional accounts, positional, so a skipped one still needs a
+        /// placeholder when a later one is passed:
+        ///
+        /// 10. `[writable]` Token mint representing the investment receipt.
+        /// 11. `[writable]` Token account associated with the mint, owned by the
+        ///                  depositor authority and kept frozen for raises.
+        /// 12. `[writable]` Payback claim, program derived from the fund and depositor authority
+        /// 13. `[writable]` Allocation, program derived from the fund and depositor authority
+        /// 14. `[]`        Whitelist, followed by the rest of its pages
+        Deposit {
+            amount: u64,
+            /// merkle proof of the depositor, when the whitelist is a merkle root
+            proof: Vec<[u8; 32]>,
+        },
+        /// Withdraw funds from program account. Raise funds can only send
+        /// funds out of the vault through proposals, campaigns only once their
+        /// goal is reached.
         ///
         /// 0. `[writable]` Tokenvault
         /// 1. `[writable]` Fund to transfer tokens out of
-        /// 2. `[signer]`   Account to withdraw to
+        /// 2. `[signer]`   FundOwner
         /// 3. `[]`         Fund Authority
         /// 4. `[]`         SPL token program
+        /// 5. `[]`         Clock sysvar
+        /// 6. `[writable]` Token account of the fund mint to withdraw to
+        /// .. `[signer]`   Multisig signers when the FundOwner is a multisig
         Withdraw { amount: u64 },
         /// Close fund, prohibit deposits
         ///
         /// 0. `[writable]` Fund
-        /// 2. `[signer]`   FundOwner
+        /// 2. `[signer]`   FundOwner or FundAuthority
+        /// .. `[signer]`   Multisig signers when the signer is a multisig
         Close,
         /// Add a new entry to the Whitelist of a fund.
         ///
         /// 0. `[writable]` Fund
-        /// 1. `[signer]`   FundOwner
-        /// 2. `[writable]` whitelist
+        /// 1. `[signer]`   FundOwner or FundAuthority
+        /// 2. `[writable]` whitelist, followed by the rest of its pages
+        /// .. `[signer]`   Multisig signers when the signer is a multisig
         WhitelistAdd { entry: Pubkey },
         /// Removes an entry from the funds Whitelist.
         ///
         /// 0. `[writable]` Fund
-        /// 1. `[signer]`   FundOwner
-        /// 2. `[writable]` whitelist
+        /// 1. `[signer]`   FundOwner or FundAuthority
+        /// 2. `[writable]` whitelist, followed by the rest of its pages
+        /// .. `[signer]`   Multisig signers when the signer is a multisig
         WhitelistDelete { entry: Pubkey },
         /// InitilaizePayback creates a program address to pay back token holders
         ///
         /// 0. `[writable]` Fund
-        /// 1. `[signer]`   Depositor auhtority.
+        /// 1. `[signer]`   FundOwner or FundAuthority
+        /// 2. `[writable, signer]` Payer of the payback round account
+        /// 3. `[writable]` Payback round, program derived from the fund and round
+        /// 4. `[]`         System program
+        /// 5. `[]`         Rent sysvar
+        /// .. `[signer]`   Multisig signers when the signer is a multisig
         RegisterPayback { amount: u64 },
+        /// Deposits the total of a registered payback round into the payback vault.
+        ///
+        /// 0. `[writable]` Fund
+        /// 1. `[signer]`   FundOwner
+        /// 2. `[writable]` Payback round
+        /// 3. `[writable]` Token account to take the payback from
+        /// 4. `[writable]` Payback vault
+        /// 5. `[]`         SPL token program
+        /// .. `[signer]`   Multisig signers when the FundOwner is a multisig
+        DepositPayback { round: u32 },
+        /// Claims the paybacks a share holder earned since the last claim. Paybacks
+        /// are paid on the shares of the claim, which always match the receipts
+        /// held since raises keep them frozen to the depositor.
+        ///
+        /// 0. `[]`         Fund
+        /// 1. `[writable]` Payback vault
+        /// 2. `[]`         Tokenvault authority
+        /// 3. `[writable, signer]` Share holder authority, pays for a missing position
+        /// 4. `[writable]` Payback claim, program derived from the fund and holder
+        /// 5. `[writable]` Token account to send the payback to
+        /// 6. `[]`         SPL token program
+        /// 7. `[writable]` Position, program derived from the fund and holder
+        /// 8. `[]`         System program, only when the position doesn't exist yet
+        /// 9. `[]`         Rent sysvar, only when the position doesn't exist yet
+        ClaimPayback,
+        /// Creates a proposal to send tokens from the vault to a recipient.
+        ///
+        /// 0. `[writable]` Proposal to initialize
+        /// 1. `[]`         Fund
+        /// 2. `[signer]`   Proposer authority
+        /// 3. `[]`         Proposer token account of the share mint
+        /// 4. `[]`         Rent sysvar
+        /// 5. `[]`         Clock sysvar
+        CreateProposal {
+            /// Amount to send from the vault
+            amount: u64,
+            /// Token account receiving the funds
+            recipient: Pubkey,
+            /// Hash of the off-chain description
+            description_hash: [u8; 32],
+        },
+        /// Cancels an active proposal.
+        ///
+        /// 0. `[writable]` Proposal
+        /// 1. `[]`         Fund
+        /// 2. `[signer]`   Proposer authority
+        CancelProposal,
+        /// Casts a vote on an active proposal weighted by the shares the voter
+        /// deposited and still holds.
+        ///
+        /// 0. `[writable]` Proposal
+        /// 1. `[]`         Fund
+        /// 2. `[writable, signer]` Voter authority, pays for the vote record
+        /// 3. `[]`         Voter token account of the share mint
+        /// 4. `[writable]` Vote record, program derived from the proposal and voter
+        /// 5. `[]`         System program
+        /// 6. `[]`         Rent sysvar
+        /// 7. `[]`         Clock sysvar
+        /// 8. `[writable]` Payback claim, program derived from the fund and voter
+        CastVote { vote: accounts::vote_record::Vote },
+        /// Closes voting on a proposal once its voting period is over and
+        /// records whether it succeeded.
+        ///
+        /// 0. `[writable]` Proposal
+        /// 1. `[]`         Fund
+        /// 2. `[]`         Clock sysvar
+        FinalizeProposal,
+        /// Sends the funds of a succeeded proposal from the vault to the recipient
+        /// once the execution delay of the fund has passed.
+        ///
+        /// 0. `[writable]` Proposal
+        /// 1. `[writable]` Fund
+        /// 2. `[writable]` Tokenvault
+        /// 3. `[]`         Tokenvault authority
+        /// 4. `[writable]` Recipient token account
+        /// 5. `[]`         SPL token program
+        /// 6. `[]`         Clock sysvar
+        ExecuteProposal,
+        /// Burns shares of a fund in exchange for their pro-rata slice of the vault.
+        /// Votes of the holder on proposals still voting lose the burned shares,
+        /// so while the holder has open votes every vote record counted on the
+        /// payback claim has to be passed.
+        ///
+        /// 0. `[writable]` Fund
+        /// 1. `[writable]` Tokenvault
+        /// 2. `[]`         Tokenvault authority
+        /// 3. `[signer]`   Share holder authority
+        /// 4. `[writable]` Share holder token account of the share mint
+        /// 5. `[writable]` Token mint representing the investment receipt.
+        /// 6. `[writable]` Token account to send the vault tokens to
+        /// 7. `[]`         SPL token program
+        /// 8. `[writable]` Payback claim, program derived from the fund and holder
+        /// 9. `[]`         Clock sysvar
+        /// 10. `[writable]` Position, program derived from the fund and holder
+        /// .. `[writable]` Pairs of proposal and vote record of the open votes
+        RageQuit { shares: u64 },
+        /// Burns the receipts of a campaign that missed its goal and returns the deposit.
+        ///
+        /// 0. `[writable]` Fund
+        /// 1. `[writable]` Tokenvault
+        /// 2. `[]`         Tokenvault authority
+        /// 3. `[signer]`   Depositor authority
+        /// 4. `[writable]` Depositor token account of the receipt mint
+        /// 5. `[writable]` Token mint representing the investment receipt.
+        /// 6. `[writable]` Token account to send the deposit to
+        /// 7. `[]`         SPL token program
+        /// 8. `[]`         Clock sysvar
+        /// 9. `[writable]` Position, program derived from the fund and depositor
+        Refund { amount: u64 },
+        /// Reopens a closed fund for deposits, as long as nothing was withdrawn from it.
+        /// A fund past its deposit window counts as closed and can be extended.
+        ///
+        /// 0. `[writable]` Fund
+        /// 1. `[signer]`   FundOwner
+        /// 2. `[]`         Clock sysvar
+        /// .. `[signer]`   Multisig signers when the FundOwner is a multisig
+        Reopen {
+            /// raises the max balance of the fund
+            new_max_balance: Option<u64>,
+            /// unix timestamp deposits close at
+            new_closes_at: Option<i64>,
+        },
+        /// Updates the parameters of a fund set at initialization.
+        ///
+        /// 0. `[writable]` Fund
+        /// 1. `[signer]`   FundOwner
+        /// 2. `[writable]` Whitelist, only passed when turning a raise private
+        /// .. `[signer]`   Multisig signers when the FundOwner is a multisig
+        UpdateConfig {
+            /// max size of the fund, can't drop below its balance
+            max_balance: Option<u64>,
+            /// toggles a raise between private and public
+            private: Option<bool>,
+            /// authority of the fund
+            authority: Option<Pubkey>,
+            /// guardian allowed to pause the fund, only while the fund has none
+            guardian: Option<Pubkey>,
+            /// turns allocations off, opening deposits to every depositor again
+            allocations: Option<bool>,
+        },
+        /// Proposes a new owner of the fund, who has to accept the ownership.
+        /// Proposing the default pubkey cancels a pending transfer.
+        ///
+        /// 0. `[writable]` Fund
+        /// 1. `[signer]`   FundOwner
+        /// .. `[signer]`   Multisig signers when the FundOwner is a multisig
+        ProposeOwner { new_owner: Pubkey },
+        /// Accepts the ownership of a fund proposed with ProposeOwner.
+        ///
+        /// 0. `[writable]` Fund
+        /// 1. `[signer]`   Pending owner
+        /// .. `[signer]`   Multisig signers when the pending owner is a multisig
+        AcceptOwnership,
+        /// Freezes deposits, withdrawals, proposals and paybacks of a fund.
+        /// Rage quits and refunds stay available. After two weeks the owner
+        /// can replace the guardian and lift the pause with SetGuardian.
+        ///
+        /// 0. `[writable]` Fund
+        /// 1. `[signer]`   Guardian
+        /// 2. `[]`         Clock sysvar
+        Pause,
+        /// Lifts a pause.
+        ///
+        /// 0. `[writable]` Fund
+        /// 1. `[signer]`   Guardian
+        /// 2. `[]`         Clock sysvar
+        Unpause,
+        /// Grows a whitelist by chaining a new page after its last page. The
+        /// capacity of the page follows from the size of the account.
+        ///
+        /// 0. `[]`         Fund
+        /// 1. `[signer]`   FundOwner or FundAuthority
+        /// 2. `[writable]` Last page of the whitelist
+        /// 3. `[writable]` Whitelist page to initialize
+        /// .. `[signer]`   Multisig signers when the signer is a multisig
+        WhitelistResize,
+        /// Sets the merkle root of the depositors allowed into a private raise.
+        /// Leaves are the hashed depositor keys, pairs are hashed in sorted
+        /// order. A zero root goes back to the whitelist pages.
+        ///
+        /// 0. `[writable]` Fund
+        /// 1. `[signer]`   FundOwner or FundAuthority
+        /// .. `[signer]`   Multisig signers when the signer is a multisig
+        SetWhitelistRoot { root: [u8; 32] },
+        /// Sets the least and most a depositor can deposit into the fund in
+        /// total, creating their allocation on first use. From the first
+        /// allocation on, only depositors holding one can deposit, until
+        /// allocations are turned off with UpdateConfig.
+        ///
+        /// 0. `[writable]` Fund
+        /// 1. `[signer]`   FundOwner or FundAuthority
+        /// 2. `[writable, signer]` Payer of the allocation account
+        /// 3. `[writable]` Allocation, program derived from the fund and depositor
+        /// 4. `[]`         System program
+        /// 5. `[]`         Rent sysvar
+        /// .. `[signer]`   Multisig signers when the signer is a multisig
+        SetAllocation {
+            depositor: Pubkey,
+            min: u64,
+            max: u64,
+        },
+        /// Hands the guardian role over to a new key. Setting the default
+        /// pubkey leaves the fund without a guardian. The owner can sign in
+        /// place of the guardian once the fund has been paused for two weeks,
+        /// which also lifts the pause.
+        ///
+        /// 0. `[writable]` Fund
+        /// 1. `[signer]`   Guardian, or FundOwner of a fund paused for too long
+        /// 2. `[]`         Clock sysvar
+        /// .. `[signer]`   Multisig signers when the FundOwner is a multisig
+        SetGuardian { guardian: Pubkey },
     }
 }
 
@@ -0,0 +1,78 @@
+use crate::access_control;
+use fund::{
+    accounts::fund::Fund,
+    error::{FundError, FundErrorCode},
+};
+use serum_common::pack::Pack;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    msg,
+    pubkey::Pubkey,
+};
+
+pub fn handler(program_id: &Pubkey, accounts: &[AccountInfo]) -> Result<(), FundError> {
+    msg!("handler: accept_ownership");
+    let acc_infos = &mut accounts.iter();
+
+    let fund_acc_info = next_account_info(acc_infos)?;
+    let pending_owner_acc_info = next_account_info(acc_infos)?;
+    let signer_acc_infos = acc_infos.as_slice();
+
+    access_control(AccessControlRequest {
+        program_id,
+        fund_acc_info,
+        pending_owner_acc_info,
+        signer_acc_infos,
+    })?;
+
+    Fund::unpack_mut(
+        &mut fund_acc_info.try_borrow_mut_data()?,
+        &mut |fund_acc: &mut Fund| {
+            state_transition(StateTransistionRequest { fund_acc }).map_err(Into::into)
+        },
+    )?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<(), FundError> {
+    let AccessControlRequest {
+        program_id,
+        fund_acc_info,
+        pending_owner_acc_info,
+        signer_acc_infos,
+    } = req;
+
+    let fund = access_control::fund(fund_acc_info, program_id)?;
+    access_control::not_paused(&fund)?;
+    if fund.pending_owner == Pubkey::default() || fund.pending_owner != *pending_owner_acc_info.key
+    {
+        return Err(FundErrorCode::InvalidPendingOwner.into());
+    }
+    access_control::check_signer(pending_owner_acc_info, signer_acc_infos)?;
+
+    msg!("access control accept_ownership success");
+
+    Ok(())
+}
+
+fn state_transition(req: StateTransistionRequest) -> Result<(), FundError> {
+    let StateTransistionRequest { fund_acc } = req;
+
+    fund_acc.accept_ownership();
+
+    msg!("state transition accept_ownership success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a, 'b> {
+    program_id: &'a Pubkey,
+    fund_acc_info: &'a AccountInfo<'b>,
+    pending_owner_acc_info: &'a AccountInfo<'b>,
+    signer_acc_infos: &'a [AccountInfo<'b>],
+}
+
+struct StateTransistionRequest<'c> {
+    fund_acc: &'c mut Fund,
+}
@@ -1,14 +1,26 @@
 use fund::{
-    accounts::{fund::Fund, vault::TokenVault, whitelist::Whitelist},
+    accounts::{
+        allocation::Allocation, fund::Fund, payback::Payback, payback_claim::PaybackClaim,
+        position::Position, proposal::Proposal, vault::TokenVault, vote_record::VoteRecord,
+        whitelist::Whitelist,
+    },
     error::{FundError, FundErrorCode},
 };
 use serum_common::pack::Pack;
 use solana_program::sysvar::Sysvar;
 use solana_program::{
-    account_info::AccountInfo, program_pack::Pack as TokenPack, pubkey::Pubkey, sysvar::rent::Rent,
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    hash::hashv,
+    program_pack::Pack as TokenPack,
+    pubkey::Pubkey,
+    sysvar::rent::Rent,
 };
 
-use spl_token::state::{Account as TokenAccount, Mint};
+use spl_token::{
+    instruction::MAX_SIGNERS,
+    state::{Account as TokenAccount, Mint, Multisig},
+};
 
 pub fn token(acc_info: &AccountInfo) -> Result<TokenAccount, FundError> {
     if *acc_info.owner != spl_token::ID {
@@ -23,6 +35,20 @@ pub fn token(acc_info: &AccountInfo) -> Result<TokenAccount, FundError> {
     Ok(token)
 }
 
+/// Returns a token account holding fund receipts, which raises keep frozen.
+pub fn receipt_token(acc_info: &AccountInfo) -> Result<TokenAccount, FundError> {
+    if *acc_info.owner != spl_token::ID {
+        return Err(FundErrorCode::InvalidAccountOwner.into());
+    }
+
+    let token = TokenAccount::unpack(&acc_info.try_borrow_data()?)?;
+    if token.state == spl_token::state::AccountState::Uninitialized {
+        return Err(FundErrorCode::NotInitialized.into());
+    }
+
+    Ok(token)
+}
+
 pub fn fund(acc_info: &AccountInfo, program_id: &Pubkey) -> Result<Fund, FundError> {
     if acc_info.owner != program_id {
         return Err(FundErrorCode::InvalidAccountOwner.into());
@@ -36,46 +62,166 @@ pub fn fund(acc_info: &AccountInfo, program_id: &Pubkey) -> Result<Fund, FundErr
     Ok(fund)
 }
 
+/// whitelist_pages takes the pages of a whitelist off the accounts, following
+/// the chain from the first page. Returns no pages when no accounts are left.
+pub fn whitelist_pages<'a, 'b>(
+    acc_infos: &mut std::slice::Iter<'a, AccountInfo<'b>>,
+) -> Result<Vec<&'a AccountInfo<'b>>, FundError> {
+    let mut pages = vec![];
+    let mut page = match acc_infos.next() {
+        Some(page) => page,
+        None => return Ok(pages),
+    };
+    loop {
+        let next = Whitelist::new(page.clone())?.next()?;
+        pages.push(page);
+        if next == Pubkey::default() {
+            break;
+        }
+        page = next_account_info(acc_infos)?;
+        if *page.key != next {
+            return Err(FundErrorCode::InvalidWhitelist.into());
+        }
+    }
+
+    Ok(pages)
+}
+
+/// whitelist checks the pages are the whitelist of the fund, starting at its
+/// first page.
 pub fn whitelist<'a>(
-    wl_acc_info: AccountInfo<'a>,
+    wl_acc_infos: &[&AccountInfo<'a>],
+    fund_addr: &Pubkey,
     fund: &Fund,
     program_id: &Pubkey,
+) -> Result<Vec<Whitelist<'a>>, FundError> {
+    let first = wl_acc_infos
+        .first()
+        .ok_or(FundErrorCode::InvalidWhitelist)?;
+    if fund.whitelist != *first.key {
+        return Err(FundErrorCode::InvalidWhitelist.into());
+    }
+
+    let mut pages = vec![];
+    for wl_acc_info in wl_acc_infos.iter() {
+        pages.push(whitelist_page(wl_acc_info, fund_addr, program_id)?);
+    }
+
+    Ok(pages)
+}
+
+/// whitelist_page checks a single page belongs to the whitelist of the fund.
+pub fn whitelist_page<'a>(
+    wl_acc_info: &AccountInfo<'a>,
+    fund_addr: &Pubkey,
+    program_id: &Pubkey,
 ) -> Result<Whitelist<'a>, FundError> {
     if program_id != wl_acc_info.owner {
         return Err(FundErrorCode::InvalidAccountOwner.into());
     }
 
-    if fund.whitelist != *wl_acc_info.key {
+    let wl = Whitelist::new(wl_acc_info.clone())?;
+    if wl.fund()? != *fund_addr {
         return Err(FundErrorCode::InvalidWhitelist.into());
     }
-    Whitelist::new(wl_acc_info).map_err(Into::into)
+
+    Ok(wl)
 }
 
 pub fn check_owner(
     program_id: &Pubkey,
     acc_info: &AccountInfo,
     owner_acc_info: &AccountInfo,
+    signer_acc_infos: &[AccountInfo],
 ) -> Result<(), FundError> {
-    if !owner_acc_info.is_signer {
-        return Err(FundErrorCode::Unauthorized.into());
-    }
-
     let fund = fund(acc_info, program_id)?;
 
     if !fund.owner.eq(owner_acc_info.key) {
         return Err(FundErrorCode::InvalidAccountOwner.into());
     }
 
+    check_signer(owner_acc_info, signer_acc_infos)
+}
+
+/// check_authority allows the owner or the authority of a fund, the authority
+/// runs the day-to-day operations while the owner keeps control of the vault.
+pub fn check_authority(
+    program_id: &Pubkey,
+    acc_info: &AccountInfo,
+    authority_acc_info: &AccountInfo,
+    signer_acc_infos: &[AccountInfo],
+) -> Result<(), FundError> {
+    let fund = fund(acc_info, program_id)?;
+
+    if !fund.owner.eq(authority_acc_info.key) && !fund.authority.eq(authority_acc_info.key) {
+        return Err(FundErrorCode::InvalidAuthority.into());
+    }
+
+    check_signer(authority_acc_info, signer_acc_infos)
+}
+
+/// check_signer passes when the account signed the transaction, or when it is
+/// an spl token multisig and M of its N signers are among the signer accounts.
+pub fn check_signer(
+    acc_info: &AccountInfo,
+    signer_acc_infos: &[AccountInfo],
+) -> Result<(), FundError> {
+    if acc_info.is_signer {
+        return Ok(());
+    }
+    if *acc_info.owner != spl_token::ID || acc_info.try_data_len()? != Multisig::LEN {
+        return Err(FundErrorCode::Unauthorized.into());
+    }
+
+    let multisig = Multisig::unpack(&acc_info.try_borrow_data()?)?;
+    let mut matched = [false; MAX_SIGNERS];
+    let mut num_signers = 0;
+    for signer in signer_acc_infos.iter() {
+        for (position, key) in multisig.signers[0..multisig.n as usize].iter().enumerate() {
+            if key == signer.key && !matched[position] {
+                if !signer.is_signer {
+                    return Err(FundErrorCode::Unauthorized.into());
+                }
+                matched[position] = true;
+                num_signers += 1;
+            }
+        }
+    }
+    if num_signers < multisig.m {
+        return Err(FundErrorCode::NotEnoughSigners.into());
+    }
+
     Ok(())
 }
 
-pub fn fund_open(acc_info: &AccountInfo, program_id: &Pubkey) -> Result<(), FundError> {
+/// not_paused fails while the guardian has the fund paused
+pub fn not_paused(fund: &Fund) -> Result<(), FundError> {
+    if fund.paused {
+        return Err(FundErrorCode::FundPaused.into());
+    }
+
+    Ok(())
+}
+
+pub fn fund_open(
+    acc_info: &AccountInfo,
+    program_id: &Pubkey,
+    clock_acc_info: &AccountInfo,
+) -> Result<(), FundError> {
     let fund = fund(acc_info, program_id)?;
 
     if !fund.open {
         return Err(FundErrorCode::FundClosed.into());
     }
 
+    let clock = clock(clock_acc_info)?;
+    if fund.opens_at != 0 && clock.unix_timestamp < fund.opens_at {
+        return Err(FundErrorCode::FundNotYetOpen.into());
+    }
+    if fund.closes_at != 0 && clock.unix_timestamp >= fund.closes_at {
+        return Err(FundErrorCode::DepositWindowClosed.into());
+    }
+
     Ok(())
 }
 
@@ -99,6 +245,13 @@ pub fn rent(acc_info: &AccountInfo) -> Result<Rent, FundError> {
     Rent::from_account_info(acc_info).map_err(Into::into)
 }
 
+pub fn clock(acc_info: &AccountInfo) -> Result<Clock, FundError> {
+    if *acc_info.key != solana_program::sysvar::clock::id() {
+        return Err(FundErrorCode::InvalidClockSysvar.into());
+    }
+    Clock::from_account_info(acc_info).map_err(Into::into)
+}
+
 pub fn vault(
     acc_info: &AccountInfo,
     vault_authority_acc_info: &AccountInfo,
@@ -158,6 +311,32 @@ pub fn vault_join(
     Ok(vault)
 }
 
+pub fn payback_vault(
+    acc_info: &AccountInfo,
+    vault_authority_acc_info: &AccountInfo,
+    fund_acc_info: &AccountInfo,
+    program_id: &Pubkey,
+) -> Result<TokenAccount, FundError> {
+    let fund = fund(fund_acc_info, program_id)?;
+    let payback_vault = token(acc_info)?;
+    if *acc_info.key != fund.payback_vault {
+        return Err(FundErrorCode::InvalidPaybackVault.into());
+    }
+
+    let va = vault_authority(
+        vault_authority_acc_info,
+        fund_acc_info.key,
+        &fund,
+        program_id,
+    )?;
+
+    if va != payback_vault.owner {
+        return Err(FundErrorCode::InvalidPaybackVault.into());
+    }
+
+    Ok(payback_vault)
+}
+
 pub fn vault_authority(
     vault_authority_acc_info: &AccountInfo,
     fund_addr: &Pubkey,
@@ -176,10 +355,14 @@ pub fn vault_authority(
     Ok(va)
 }
 
+/// withdraw checks the owner of the fund signed the withdrawal and the tokens
+/// go to a token account of the fund mint.
 pub fn withdraw(
     program_id: &Pubkey,
     fund_acc_info: &AccountInfo,
     withdraw_acc_beneficiary_info: &AccountInfo,
+    destination_acc_info: &AccountInfo,
+    signer_acc_infos: &[AccountInfo],
 ) -> Result<Fund, FundError> {
     let fund = Fund::unpack(&fund_acc_info.try_borrow_data()?)?;
 
@@ -190,49 +373,86 @@ pub fn withdraw(
         return Err(FundErrorCode::NotInitialized.into());
     }
     if fund.owner != *withdraw_acc_beneficiary_info.key {
-        return Err(FundErrorCode::Unauthorized.into());
+        return Err(FundErrorCode::WithdrawUnauthorized.into());
+    }
+    check_signer(withdraw_acc_beneficiary_info, signer_acc_infos)?;
+
+    if *destination_acc_info.key == fund.vault {
+        return Err(FundErrorCode::InvalidWithdrawDestination.into());
+    }
+    let destination =
+        token(destination_acc_info).map_err(|_| FundErrorCode::InvalidWithdrawDestination)?;
+    if destination.mint != fund.mint {
+        return Err(FundErrorCode::InvalidWithdrawMint.into());
     }
 
     Ok(fund)
 }
 
-pub fn check_balance(fund_acc_info: &AccountInfo, amount: u64) -> Result<(), FundError> {
+/// Returns the part of the amount the fund can still accept, the remainder of a
+/// deposit overflowing the max balance is left with the depositor.
+pub fn check_balance(fund_acc_info: &AccountInfo, amount: u64) -> Result<u64, FundError> {
     let fund = Fund::unpack(&fund_acc_info.try_borrow_data()?)?;
 
-    if fund.balance + amount > fund.max_balance {
+    let remaining = fund.max_balance.saturating_sub(fund.balance);
+    if remaining == 0 {
         return Err(FundErrorCode::FundBalanceOverflow.into());
     }
 
-    Ok(())
+    Ok(std::cmp::min(amount, remaining))
 }
 
+/// check_depositor fails unless the depositor authority is on the whitelist
+/// of the fund, or proves it is part of its merkle root.
 pub fn check_depositor<'a>(
     program_id: &Pubkey,
-    wl_acc_info: AccountInfo<'a>,
+    wl_acc_infos: &[&AccountInfo<'a>],
+    fund_addr: &Pubkey,
     fund: &Fund,
-    depositor_acc_info: &AccountInfo<'a>,
+    depositor_authority_acc_info: &AccountInfo<'a>,
+    proof: &[[u8; 32]],
 ) -> Result<(), FundError> {
-    if program_id != wl_acc_info.owner {
-        return Err(FundErrorCode::InvalidAccountOwner.into());
-    }
-
-    if fund.whitelist != *wl_acc_info.key {
-        return Err(FundErrorCode::InvalidWhitelist.into());
+    if fund.whitelist_root != [0; 32] {
+        if !check_proof(
+            &fund.whitelist_root,
+            depositor_authority_acc_info.key,
+            proof,
+        ) {
+            return Err(FundErrorCode::InvalidWhitelistProof.into());
+        }
+        return Ok(());
     }
 
-    let wl: Result<Whitelist, FundError> = Whitelist::new(wl_acc_info).map_err(Into::into);
+    let pages = whitelist(wl_acc_infos, fund_addr, fund, program_id)?;
 
-    let _ = wl.unwrap().index_of(depositor_acc_info.key)?;
+    Whitelist::find(&pages, depositor_authority_acc_info.key)?
+        .ok_or(FundErrorCode::WhitelistNotFound)?;
 
     Ok(())
 }
 
+/// check_proof walks the merkle proof from the hashed key up to the root,
+/// hashing every pair in sorted order.
+fn check_proof(root: &[u8; 32], key: &Pubkey, proof: &[[u8; 32]]) -> bool {
+    let mut node = [0u8; 32];
+    node.copy_from_slice(hashv(&[key.as_ref()]).as_ref());
+    for sibling in proof.iter() {
+        let parent = if node <= *sibling {
+            hashv(&[&node[..], &sibling[..]])
+        } else {
+            hashv(&[&sibling[..], &node[..]])
+        };
+        node.copy_from_slice(parent.as_ref());
+    }
+    node == *root
+}
+
 pub fn check_nft<'a>(
     fund: &Fund,
     mint_acc_info: &AccountInfo<'a>,
     token_acc_info: &AccountInfo<'a>,
 ) -> Result<(), FundError> {
-    let token_acc = token(token_acc_info)?;
+    let token_acc = receipt_token(token_acc_info)?;
     if token_acc.mint != fund.nft_mint {
         return Err(FundErrorCode::InvalidTokenAccountMint.into());
     }
@@ -241,3 +461,245 @@ pub fn check_nft<'a>(
     }
     Ok(())
 }
+
+pub fn proposal(
+    acc_info: &AccountInfo,
+    fund_addr: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Proposal, FundError> {
+    if acc_info.owner != program_id {
+        return Err(FundErrorCode::InvalidAccountOwner.into());
+    }
+
+    let proposal = Proposal::unpack(&acc_info.try_borrow_data()?)?;
+    if !proposal.initialized {
+        return Err(FundErrorCode::NotInitialized.into());
+    }
+    if proposal.fund != *fund_addr {
+        return Err(FundErrorCode::InvalidProposal.into());
+    }
+
+    Ok(proposal)
+}
+
+pub fn vote_record(
+    acc_info: &AccountInfo,
+    proposal_addr: &Pubkey,
+    voter: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<VoteRecord, FundError> {
+    if acc_info.owner != program_id {
+        return Err(FundErrorCode::InvalidVoteRecord.into());
+    }
+
+    let vote_record = VoteRecord::unpack(&acc_info.try_borrow_data()?)?;
+    if !vote_record.initialized {
+        return Err(FundErrorCode::NotInitialized.into());
+    }
+    if vote_record.proposal != *proposal_addr || vote_record.voter != *voter {
+        return Err(FundErrorCode::InvalidVoteRecord.into());
+    }
+
+    Ok(vote_record)
+}
+
+/// Returns the number of fund shares held by the token account, checking that
+/// it holds the share mint and belongs to the given authority.
+pub fn shares(
+    fund: &Fund,
+    token_acc_info: &AccountInfo,
+    authority: &Pubkey,
+) -> Result<u64, FundError> {
+    let token_acc = receipt_token(token_acc_info)?;
+    if token_acc.mint != fund.nft_mint {
+        return Err(FundErrorCode::InvalidTokenAccountMint.into());
+    }
+    if token_acc.owner != *authority {
+        return Err(FundErrorCode::InvalidAccountOwner.into());
+    }
+
+    Ok(token_acc.amount)
+}
+
+pub fn payback(
+    acc_info: &AccountInfo,
+    fund_addr: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Payback, FundError> {
+    if acc_info.owner != program_id {
+        return Err(FundErrorCode::InvalidPaybackRound.into());
+    }
+
+    let payback = Payback::unpack(&acc_info.try_borrow_data()?)?;
+    if !payback.initialized {
+        return Err(FundErrorCode::NotInitialized.into());
+    }
+    if payback.fund != *fund_addr {
+        return Err(FundErrorCode::InvalidPaybackRound.into());
+    }
+
+    Ok(payback)
+}
+
+pub fn payback_claim(
+    acc_info: &AccountInfo,
+    fund_addr: &Pubkey,
+    holder: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<PaybackClaim, FundError> {
+    if acc_info.owner != program_id {
+        return Err(FundErrorCode::InvalidPaybackClaim.into());
+    }
+
+    let claim = PaybackClaim::unpack(&acc_info.try_borrow_data()?)?;
+    if !claim.initialized {
+        return Err(FundErrorCode::NotInitialized.into());
+    }
+    if claim.fund != *fund_addr || claim.holder != *holder {
+        return Err(FundErrorCode::InvalidPaybackClaim.into());
+    }
+
+    Ok(claim)
+}
+
+pub fn position(
+    acc_info: &AccountInfo,
+    fund_addr: &Pubkey,
+    depositor: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Position, FundError> {
+    if acc_info.owner != program_id {
+        return Err(FundErrorCode::InvalidPosition.into());
+    }
+
+    let position = Position::unpack(&acc_info.try_borrow_data()?)?;
+    if !position.initialized {
+        return Err(FundErrorCode::NotInitialized.into());
+    }
+    if position.fund != *fund_addr || position.depositor != *depositor {
+        return Err(FundErrorCode::InvalidPosition.into());
+    }
+
+    Ok(position)
+}
+
+/// position_if_any returns the position of the depositor, or None when the
+/// account is the unused position address of a depositor without one.
+pub fn position_if_any(
+    acc_info: &AccountInfo,
+    fund_addr: &Pubkey,
+    depositor: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Option<Position>, FundError> {
+    if acc_info.owner == program_id {
+        return position(acc_info, fund_addr, depositor, program_id).map(Some);
+    }
+    let (addr, _) =
+        Pubkey::find_program_address(&Position::seeds(fund_addr, depositor), program_id);
+    if addr != *acc_info.key {
+        return Err(FundErrorCode::InvalidPosition.into());
+    }
+    Ok(None)
+}
+
+pub fn allocation(
+    acc_info: &AccountInfo,
+    fund_addr: &Pubkey,
+    depositor: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Allocation, FundError> {
+    if acc_info.owner != program_id {
+        return Err(FundErrorCode::InvalidAllocation.into());
+    }
+
+    let allocation = Allocation::unpack(&acc_info.try_borrow_data()?)?;
+    if !allocation.initialized {
+        return Err(FundErrorCode::NotInitialized.into());
+    }
+    if allocation.fund != *fund_addr || allocation.depositor != *depositor {
+        return Err(FundErrorCode::InvalidAllocation.into());
+    }
+
+    Ok(allocation)
+}
+
+/// Returns the part of the amount left within the allocation of the depositor,
+/// failing when it is used up or the total stays below its minimum.
+pub fn check_allocation(allocation: &Allocation, amount: u64) -> Result<u64, FundError> {
+    let amount = std::cmp::min(amount, allocation.remaining());
+    if amount == 0 {
+        return Err(FundErrorCode::AllocationExceeded.into());
+    }
+    if allocation.deposited + amount < allocation.min {
+        return Err(FundErrorCode::BelowMinimumContribution.into());
+    }
+    Ok(amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_program::hash::Hash;
+
+    fn key(byte: u8) -> Pubkey {
+        Pubkey::new_from_array([byte; 32])
+    }
+
+    fn bytes(hash: Hash) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        out.copy_from_slice(hash.as_ref());
+        out
+    }
+
+    fn leaf(key: &Pubkey) -> [u8; 32] {
+        bytes(hashv(&[key.as_ref()]))
+    }
+
+    fn parent(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+        bytes(hashv(&[&lo[..], &hi[..]]))
+    }
+
+    #[test]
+    fn valid_proof() {
+        let keys: Vec<Pubkey> = (1..5).map(key).collect();
+        let leaves: Vec<[u8; 32]> = keys.iter().map(leaf).collect();
+        let left = parent(&leaves[0], &leaves[1]);
+        let right = parent(&leaves[2], &leaves[3]);
+        let root = parent(&left, &right);
+
+        assert!(check_proof(&root, &keys[0], &[leaves[1], right]));
+        assert!(check_proof(&root, &keys[3], &[leaves[2], left]));
+    }
+
+    #[test]
+    fn wrong_sibling() {
+        let keys: Vec<Pubkey> = (1..5).map(key).collect();
+        let leaves: Vec<[u8; 32]> = keys.iter().map(leaf).collect();
+        let left = parent(&leaves[0], &leaves[1]);
+        let right = parent(&leaves[2], &leaves[3]);
+        let root = parent(&left, &right);
+
+        assert!(!check_proof(&root, &keys[0], &[leaves[2], right]));
+        assert!(!check_proof(&root, &keys[0], &[leaves[1]]));
+        assert!(!check_proof(&root, &key(9), &[leaves[1], right]));
+    }
+
+    #[test]
+    fn empty_proof_single_leaf() {
+        let root = leaf(&key(1));
+
+        assert!(check_proof(&root, &key(1), &[]));
+        assert!(!check_proof(&root, &key(9), &[]));
+    }
+
+    #[test]
+    fn sibling_order_independent() {
+        let (a, b) = (key(1), key(2));
+        let root = parent(&leaf(&a), &leaf(&b));
+
+        assert_eq!(root, parent(&leaf(&b), &leaf(&a)));
+        assert!(check_proof(&root, &a, &[leaf(&b)]));
+        assert!(check_proof(&root, &b, &[leaf(&a)]));
+    }
+}
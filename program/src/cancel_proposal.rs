@@ -0,0 +1,87 @@
+use crate::access_control;
+use fund::{
+    accounts::proposal::Proposal,
+    error::{FundError, FundErrorCode},
+};
+use serum_common::pack::Pack;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    msg,
+    pubkey::Pubkey,
+};
+use std::convert::Into;
+
+pub fn handler(program_id: &Pubkey, accounts: &[AccountInfo]) -> Result<(), FundError> {
+    msg!("handler: cancel_proposal");
+
+    let acc_infos = &mut accounts.iter();
+
+    let proposal_acc_info = next_account_info(acc_infos)?;
+    let fund_acc_info = next_account_info(acc_infos)?;
+    let proposer_acc_info = next_account_info(acc_infos)?;
+
+    access_control(AccessControlRequest {
+        program_id,
+        proposal_acc_info,
+        fund_acc_info,
+        proposer_acc_info,
+    })?;
+
+    Proposal::unpack_mut(
+        &mut proposal_acc_info.try_borrow_mut_data()?,
+        &mut |proposal_acc: &mut Proposal| {
+            state_transistion(StateTransistionRequest { proposal_acc }).map_err(Into::into)
+        },
+    )?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<(), FundError> {
+    let AccessControlRequest {
+        program_id,
+        proposal_acc_info,
+        fund_acc_info,
+        proposer_acc_info,
+    } = req;
+
+    if !proposer_acc_info.is_signer {
+        return Err(FundErrorCode::Unauthorized.into());
+    }
+
+    let fund = access_control::fund(fund_acc_info, program_id)?;
+    access_control::not_paused(&fund)?;
+    let proposal = access_control::proposal(proposal_acc_info, fund_acc_info.key, program_id)?;
+
+    if proposal.proposer != *proposer_acc_info.key {
+        return Err(FundErrorCode::Unauthorized.into());
+    }
+    if !proposal.is_active() {
+        return Err(FundErrorCode::ProposalNotActive.into());
+    }
+
+    msg!("access control cancel_proposal success");
+
+    Ok(())
+}
+
+fn state_transistion(req: StateTransistionRequest) -> Result<(), FundError> {
+    let StateTransistionRequest { proposal_acc } = req;
+
+    proposal_acc.cancel();
+
+    msg!("state transition cancel_proposal success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a, 'b> {
+    program_id: &'a Pubkey,
+    proposal_acc_info: &'a AccountInfo<'b>,
+    fund_acc_info: &'a AccountInfo<'b>,
+    proposer_acc_info: &'a AccountInfo<'b>,
+}
+
+struct StateTransistionRequest<'c> {
+    proposal_acc: &'c mut Proposal,
+}
@@ -0,0 +1,205 @@
+use crate::{access_control, pda};
+use fund::{
+    accounts::{
+        payback_claim::PaybackClaim,
+        proposal::Proposal,
+        vote_record::{Vote, VoteRecord},
+    },
+    error::{FundError, FundErrorCode},
+};
+use serum_common::pack::Pack;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    msg,
+    pubkey::Pubkey,
+};
+use std::convert::Into;
+
+pub fn handler(program_id: &Pubkey, accounts: &[AccountInfo], vote: Vote) -> Result<(), FundError> {
+    msg!("handler: cast_vote");
+
+    let acc_infos = &mut accounts.iter();
+
+    let proposal_acc_info = next_account_info(acc_infos)?;
+    let fund_acc_info = next_account_info(acc_infos)?;
+    let voter_acc_info = next_account_info(acc_infos)?;
+    let voter_token_acc_info = next_account_info(acc_infos)?;
+    let vote_record_acc_info = next_account_info(acc_infos)?;
+    let system_program_acc_info = next_account_info(acc_infos)?;
+    let rent_acc_info = next_account_info(acc_infos)?;
+    let clock_acc_info = next_account_info(acc_infos)?;
+    let payback_claim_acc_info = next_account_info(acc_infos)?;
+
+    let AccessControlResponse {
+        weight,
+        nonce,
+        voting_ends_at,
+        now,
+    } = access_control(AccessControlRequest {
+        program_id,
+        proposal_acc_info,
+        fund_acc_info,
+        voter_acc_info,
+        voter_token_acc_info,
+        vote_record_acc_info,
+        clock_acc_info,
+        payback_claim_acc_info,
+    })?;
+
+    // create the vote record, failing if the voter already voted
+    {
+        let rent = access_control::rent(rent_acc_info)?;
+        let space = VoteRecord::default().size()?;
+        let signer_seeds =
+            VoteRecord::signer_seeds(proposal_acc_info.key, voter_acc_info.key, &nonce);
+        pda::create_account(
+            program_id,
+            voter_acc_info,
+            vote_record_acc_info,
+            system_program_acc_info,
+            &rent,
+            space,
+            &signer_seeds,
+        )?;
+    }
+
+    VoteRecord::unpack_mut(
+        &mut vote_record_acc_info.try_borrow_mut_data()?,
+        &mut |vote_record_acc: &mut VoteRecord| {
+            vote_record_acc.initialized = true;
+            vote_record_acc.proposal = *proposal_acc_info.key;
+            vote_record_acc.voter = *voter_acc_info.key;
+            vote_record_acc.vote = vote;
+            vote_record_acc.weight = weight;
+            vote_record_acc.cast_at = now;
+            Ok(())
+        },
+    )?;
+
+    // keep count of the open votes, rage quits take the burned shares off them
+    PaybackClaim::unpack_mut(
+        &mut payback_claim_acc_info.try_borrow_mut_data()?,
+        &mut |claim_acc: &mut PaybackClaim| {
+            claim_acc.cast_vote(voting_ends_at, now);
+            Ok(())
+        },
+    )?;
+
+    Proposal::unpack_mut(
+        &mut proposal_acc_info.try_borrow_mut_data()?,
+        &mut |proposal_acc: &mut Proposal| {
+            state_transistion(StateTransistionRequest {
+                proposal_acc,
+                vote,
+                weight,
+            })
+            .map_err(Into::into)
+        },
+    )?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<AccessControlResponse, FundError> {
+    msg!("access-control: cast_vote");
+
+    let AccessControlRequest {
+        program_id,
+        proposal_acc_info,
+        fund_acc_info,
+        voter_acc_info,
+        voter_token_acc_info,
+        vote_record_acc_info,
+        clock_acc_info,
+        payback_claim_acc_info,
+    } = req;
+
+    if !voter_acc_info.is_signer {
+        return Err(FundErrorCode::Unauthorized.into());
+    }
+
+    let fund = access_control::fund(fund_acc_info, program_id)?;
+    access_control::not_paused(&fund)?;
+    let proposal = access_control::proposal(proposal_acc_info, fund_acc_info.key, program_id)?;
+    if !proposal.is_active() {
+        return Err(FundErrorCode::ProposalNotActive.into());
+    }
+    let clock = access_control::clock(clock_acc_info)?;
+    if clock.unix_timestamp >= proposal.voting_ends_at {
+        return Err(FundErrorCode::VotingEnded.into());
+    }
+
+    // receipts of a raise are frozen to their depositor, so the tokens held
+    // match the shares on the claim, the weight is bound by both regardless
+    let held = access_control::shares(&fund, voter_token_acc_info, voter_acc_info.key)?;
+    let claim = access_control::payback_claim(
+        payback_claim_acc_info,
+        fund_acc_info.key,
+        voter_acc_info.key,
+        program_id,
+    )?;
+    let weight = std::cmp::min(held, claim.shares);
+    if weight == 0 {
+        return Err(FundErrorCode::NotShareHolder.into());
+    }
+
+    let (vote_record, nonce) = Pubkey::find_program_address(
+        &VoteRecord::seeds(proposal_acc_info.key, voter_acc_info.key),
+        program_id,
+    );
+    if vote_record != *vote_record_acc_info.key {
+        return Err(FundErrorCode::InvalidVoteRecord.into());
+    }
+    if vote_record_acc_info.owner == program_id {
+        return Err(FundErrorCode::VoteAlreadyCast.into());
+    }
+
+    msg!("access-control: success");
+
+    Ok(AccessControlResponse {
+        weight,
+        nonce,
+        voting_ends_at: proposal.voting_ends_at,
+        now: clock.unix_timestamp,
+    })
+}
+
+fn state_transistion(req: StateTransistionRequest) -> Result<(), FundError> {
+    msg!("state-transition: cast_vote");
+
+    let StateTransistionRequest {
+        proposal_acc,
+        vote,
+        weight,
+    } = req;
+
+    proposal_acc.tally(vote, weight)?;
+
+    msg!("state-transition: success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a, 'b> {
+    program_id: &'a Pubkey,
+    proposal_acc_info: &'a AccountInfo<'b>,
+    fund_acc_info: &'a AccountInfo<'b>,
+    voter_acc_info: &'a AccountInfo<'b>,
+    voter_token_acc_info: &'a AccountInfo<'b>,
+    vote_record_acc_info: &'a AccountInfo<'b>,
+    clock_acc_info: &'a AccountInfo<'b>,
+    payback_claim_acc_info: &'a AccountInfo<'b>,
+}
+
+struct AccessControlResponse {
+    weight: u64,
+    nonce: u8,
+    voting_ends_at: i64,
+    now: i64,
+}
+
+struct StateTransistionRequest<'c> {
+    proposal_acc: &'c mut Proposal,
+    vote: Vote,
+    weight: u64,
+}
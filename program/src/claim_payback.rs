@@ -0,0 +1,248 @@
+use crate::{access_control, pda};
+use fund::{
+    accounts::{payback_claim::PaybackClaim, position::Position, vault::TokenVault, FundType},
+    error::{FundError, FundErrorCode},
+};
+use serum_common::pack::Pack;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    msg, program,
+    pubkey::Pubkey,
+};
+use spl_token::instruction;
+use std::convert::Into;
+
+pub fn handler(program_id: &Pubkey, accounts: &[AccountInfo]) -> Result<(), FundError> {
+    msg!("handler: claim_payback");
+
+    let acc_infos = &mut accounts.iter();
+
+    let fund_acc_info = next_account_info(acc_infos)?;
+    let payback_vault_acc_info = next_account_info(acc_infos)?;
+    let vault_authority_acc_info = next_account_info(acc_infos)?;
+    let holder_authority_acc_info = next_account_info(acc_infos)?;
+    let claim_acc_info = next_account_info(acc_infos)?;
+    let destination_acc_info = next_account_info(acc_infos)?;
+    let token_program_acc_info = next_account_info(acc_infos)?;
+    let position_acc_info = next_account_info(acc_infos)?;
+    let system_program_acc_info = acc_infos.next(); // optional
+    let rent_acc_info = acc_infos.next(); // optional
+
+    let AccessControlResponse {
+        payback_per_share,
+        vault_nonce,
+        position_nonce,
+    } = access_control(AccessControlRequest {
+        program_id,
+        fund_acc_info,
+        payback_vault_acc_info,
+        vault_authority_acc_info,
+        holder_authority_acc_info,
+        claim_acc_info,
+        destination_acc_info,
+        position_acc_info,
+    })?;
+
+    // holders from before positions existed, create theirs on the first claim
+    if let Some(nonce) = position_nonce {
+        let rent = access_control::rent(rent_acc_info.ok_or(FundErrorCode::InvalidRentSysvar)?)?;
+        let space = Position::default().size()?;
+        let signer_seeds =
+            Position::signer_seeds(fund_acc_info.key, holder_authority_acc_info.key, &nonce);
+        pda::create_account(
+            program_id,
+            holder_authority_acc_info,
+            position_acc_info,
+            system_program_acc_info.ok_or(FundErrorCode::InvalidAccount)?,
+            &rent,
+            space,
+            &signer_seeds,
+        )?;
+    }
+
+    PaybackClaim::unpack_mut(
+        &mut claim_acc_info.try_borrow_mut_data()?,
+        &mut |claim_acc: &mut PaybackClaim| {
+            state_transistion(StateTransistionRequest {
+                claim_acc,
+                fund_acc_info,
+                payback_vault_acc_info,
+                vault_authority_acc_info,
+                holder_authority_acc_info,
+                destination_acc_info,
+                token_program_acc_info,
+                position_acc_info,
+                payback_per_share,
+                vault_nonce,
+            })
+            .map_err(Into::into)
+        },
+    )?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<AccessControlResponse, FundError> {
+    msg!("access-control: claim_payback");
+
+    let AccessControlRequest {
+        program_id,
+        fund_acc_info,
+        payback_vault_acc_info,
+        vault_authority_acc_info,
+        holder_authority_acc_info,
+        claim_acc_info,
+        destination_acc_info,
+        position_acc_info,
+    } = req;
+
+    if !holder_authority_acc_info.is_signer {
+        return Err(FundErrorCode::Unauthorized.into());
+    }
+
+    let fund = access_control::fund(fund_acc_info, program_id)?;
+    access_control::not_paused(&fund)?;
+    if !fund.fund_type.eq(&FundType::Raise { private: true })
+        && !fund.fund_type.eq(&FundType::Raise { private: false })
+    {
+        return Err(FundErrorCode::InvalidFund.into());
+    }
+    let _ = access_control::payback_vault(
+        payback_vault_acc_info,
+        vault_authority_acc_info,
+        fund_acc_info,
+        program_id,
+    )?;
+    let _ = access_control::payback_claim(
+        claim_acc_info,
+        fund_acc_info.key,
+        holder_authority_acc_info.key,
+        program_id,
+    )?;
+
+    let position_nonce = if position_acc_info.owner == program_id {
+        let _ = access_control::position(
+            position_acc_info,
+            fund_acc_info.key,
+            holder_authority_acc_info.key,
+            program_id,
+        )?;
+        None
+    } else {
+        let (position, nonce) = Pubkey::find_program_address(
+            &Position::seeds(fund_acc_info.key, holder_authority_acc_info.key),
+            program_id,
+        );
+        if position != *position_acc_info.key {
+            return Err(FundErrorCode::InvalidPosition.into());
+        }
+        Some(nonce)
+    };
+
+    let destination = access_control::token(destination_acc_info)?;
+    if destination.mint != fund.mint {
+        return Err(FundErrorCode::InvalidTokenAccountMint.into());
+    }
+
+    msg!("access-control: success");
+
+    Ok(AccessControlResponse {
+        payback_per_share: fund.payback_per_share,
+        vault_nonce: fund.nonce,
+        position_nonce,
+    })
+}
+
+fn state_transistion(req: StateTransistionRequest) -> Result<(), FundError> {
+    msg!("state-transition: claim_payback");
+
+    let StateTransistionRequest {
+        claim_acc,
+        fund_acc_info,
+        payback_vault_acc_info,
+        vault_authority_acc_info,
+        holder_authority_acc_info,
+        destination_acc_info,
+        token_program_acc_info,
+        position_acc_info,
+        payback_per_share,
+        vault_nonce,
+    } = req;
+
+    let amount = claim_acc.claim(payback_per_share)?;
+
+    Position::unpack_mut(
+        &mut position_acc_info.try_borrow_mut_data()?,
+        &mut |position_acc: &mut Position| {
+            if !position_acc.initialized {
+                position_acc.open(
+                    *fund_acc_info.key,
+                    *holder_authority_acc_info.key,
+                    claim_acc.shares,
+                );
+            }
+            position_acc.claim(amount);
+            Ok(())
+        },
+    )?;
+
+    {
+        msg!("invoke SPL token transfer");
+        let claim_instruction = instruction::transfer(
+            &spl_token::ID,
+            payback_vault_acc_info.key,
+            destination_acc_info.key,
+            vault_authority_acc_info.key,
+            &[],
+            amount,
+        )?;
+
+        let signer_seeds = TokenVault::signer_seeds(fund_acc_info.key, &vault_nonce);
+
+        program::invoke_signed(
+            &claim_instruction,
+            &[
+                payback_vault_acc_info.clone(),
+                destination_acc_info.clone(),
+                vault_authority_acc_info.clone(),
+                token_program_acc_info.clone(),
+            ],
+            &[&signer_seeds],
+        )?;
+    }
+
+    msg!("state-transition: success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a, 'b> {
+    program_id: &'a Pubkey,
+    fund_acc_info: &'a AccountInfo<'b>,
+    payback_vault_acc_info: &'a AccountInfo<'b>,
+    vault_authority_acc_info: &'a AccountInfo<'b>,
+    holder_authority_acc_info: &'a AccountInfo<'b>,
+    claim_acc_info: &'a AccountInfo<'b>,
+    destination_acc_info: &'a AccountInfo<'b>,
+    position_acc_info: &'a AccountInfo<'b>,
+}
+
+struct AccessControlResponse {
+    payback_per_share: u128,
+    vault_nonce: u8,
+    /// nonce of the position to create for holders that have none yet
+    position_nonce: Option<u8>,
+}
+
+struct StateTransistionRequest<'a, 'b, 'c> {
+    claim_acc: &'c mut PaybackClaim,
+    fund_acc_info: &'a AccountInfo<'b>,
+    payback_vault_acc_info: &'a AccountInfo<'b>,
+    vault_authority_acc_info: &'a AccountInfo<'b>,
+    holder_authority_acc_info: &'a AccountInfo<'b>,
+    destination_acc_info: &'a AccountInfo<'b>,
+    token_program_acc_info: &'a AccountInfo<'b>,
+    position_acc_info: &'a AccountInfo<'b>,
+    payback_per_share: u128,
+    vault_nonce: u8,
+}
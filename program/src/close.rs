@@ -13,11 +13,13 @@ pub fn handler(program_id: &Pubkey, accounts: &[AccountInfo]) -> Result<(), Fund
 
     let fund_acc_info = next_account_info(acc_info)?;
     let fund_owner_acc_info = next_account_info(acc_info)?;
+    let signer_acc_infos = acc_info.as_slice();
 
     access_control(AccessControlRequest {
         program_id,
         fund_acc_info,
         fund_owner_acc_info,
+        signer_acc_infos,
     })?;
 
     Fund::unpack_mut(
@@ -35,9 +37,17 @@ fn access_control(req: AccessControlRequest) -> Result<(), FundError> {
         program_id,
         fund_acc_info,
         fund_owner_acc_info,
+        signer_acc_infos,
     } = req;
 
-    let _ = access_control::check_owner(program_id, fund_acc_info, fund_owner_acc_info)?;
+    let _ = access_control::check_authority(
+        program_id,
+        fund_acc_info,
+        fund_owner_acc_info,
+        signer_acc_infos,
+    )?;
+    let fund = access_control::fund(fund_acc_info, program_id)?;
+    access_control::not_paused(&fund)?;
 
     msg!("access control close success");
 
@@ -57,6 +67,7 @@ struct AccessControlRequest<'a, 'b> {
     program_id: &'a Pubkey,
     fund_acc_info: &'a AccountInfo<'b>,
     fund_owner_acc_info: &'a AccountInfo<'b>,
+    signer_acc_infos: &'a [AccountInfo<'b>],
 }
 
 struct StateTransistionRequest<'c> {
@@ -0,0 +1,191 @@
+use crate::access_control;
+use fund::{
+    accounts::{
+        fund::FundType,
+        proposal::{Proposal, ProposalState},
+    },
+    error::{FundError, FundErrorCode},
+};
+use serum_common::pack::Pack;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    msg,
+    pubkey::Pubkey,
+};
+use std::convert::Into;
+
+pub fn handler(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    recipient: Pubkey,
+    description_hash: [u8; 32],
+) -> Result<(), FundError> {
+    msg!("handler: create_proposal");
+
+    let acc_infos = &mut accounts.iter();
+
+    let proposal_acc_info = next_account_info(acc_infos)?;
+    let fund_acc_info = next_account_info(acc_infos)?;
+    let proposer_acc_info = next_account_info(acc_infos)?;
+    let proposer_token_acc_info = next_account_info(acc_infos)?;
+    let rent_acc_info = next_account_info(acc_infos)?;
+    let clock_acc_info = next_account_info(acc_infos)?;
+
+    let AccessControlResponse {
+        voting_ends_at,
+        fund_shares,
+    } = access_control(AccessControlRequest {
+        program_id,
+        amount,
+        proposal_acc_info,
+        fund_acc_info,
+        proposer_acc_info,
+        proposer_token_acc_info,
+        rent_acc_info,
+        clock_acc_info,
+    })?;
+
+    Proposal::unpack_mut(
+        &mut proposal_acc_info.try_borrow_mut_data()?,
+        &mut |proposal_acc: &mut Proposal| {
+            state_transistion(StateTransistionRequest {
+                proposal_acc,
+                fund: fund_acc_info.key,
+                proposer: proposer_acc_info.key,
+                recipient,
+                amount,
+                description_hash,
+                voting_ends_at,
+                fund_shares,
+            })
+            .map_err(Into::into)
+        },
+    )?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<AccessControlResponse, FundError> {
+    msg!("access-control: create_proposal");
+
+    let AccessControlRequest {
+        program_id,
+        amount,
+        proposal_acc_info,
+        fund_acc_info,
+        proposer_acc_info,
+        proposer_token_acc_info,
+        rent_acc_info,
+        clock_acc_info,
+    } = req;
+
+    if !proposer_acc_info.is_signer {
+        return Err(FundErrorCode::Unauthorized.into());
+    }
+
+    let rent = access_control::rent(rent_acc_info)?;
+    let clock = access_control::clock(clock_acc_info)?;
+
+    let proposal = Proposal::unpack(&proposal_acc_info.try_borrow_data()?)?;
+    {
+        if proposal_acc_info.owner != program_id {
+            return Err(FundErrorCode::NotOwnedByProgram.into());
+        }
+        if !rent.is_exempt(
+            proposal_acc_info.lamports(),
+            proposal_acc_info.try_data_len()?,
+        ) {
+            return Err(FundErrorCode::NotRentExempt.into());
+        }
+        if proposal.initialized {
+            return Err(FundErrorCode::AlreadyInitialized.into());
+        }
+    }
+
+    let fund = access_control::fund(fund_acc_info, program_id)?;
+    access_control::not_paused(&fund)?;
+    if !fund.fund_type.eq(&FundType::Raise { private: true })
+        && !fund.fund_type.eq(&FundType::Raise { private: false })
+    {
+        return Err(FundErrorCode::InvalidFund.into());
+    }
+    if amount > fund.balance {
+        return Err(FundErrorCode::InsufficientBalance.into());
+    }
+
+    // only share holders can create proposals
+    let shares = access_control::shares(&fund, proposer_token_acc_info, proposer_acc_info.key)?;
+    if shares == 0 {
+        return Err(FundErrorCode::NotShareHolder.into());
+    }
+
+    let voting_ends_at = clock
+        .unix_timestamp
+        .checked_add(fund.governance.voting_period)
+        .ok_or(FundErrorCode::MathOverflow)?;
+
+    msg!("access-control: success");
+
+    Ok(AccessControlResponse {
+        voting_ends_at,
+        fund_shares: fund.shares,
+    })
+}
+
+fn state_transistion(req: StateTransistionRequest) -> Result<(), FundError> {
+    msg!("state-transition: create_proposal");
+
+    let StateTransistionRequest {
+        proposal_acc,
+        fund,
+        proposer,
+        recipient,
+        amount,
+        description_hash,
+        voting_ends_at,
+        fund_shares,
+    } = req;
+
+    proposal_acc.initialized = true;
+    proposal_acc.state = ProposalState::Active;
+    proposal_acc.fund = *fund;
+    proposal_acc.proposer = *proposer;
+    proposal_acc.recipient = recipient;
+    proposal_acc.amount = amount;
+    proposal_acc.description_hash = description_hash;
+    proposal_acc.voting_ends_at = voting_ends_at;
+    proposal_acc.shares = fund_shares;
+
+    msg!("state-transition: success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a, 'b> {
+    program_id: &'a Pubkey,
+    amount: u64,
+    proposal_acc_info: &'a AccountInfo<'b>,
+    fund_acc_info: &'a AccountInfo<'b>,
+    proposer_acc_info: &'a AccountInfo<'b>,
+    proposer_token_acc_info: &'a AccountInfo<'b>,
+    rent_acc_info: &'a AccountInfo<'b>,
+    clock_acc_info: &'a AccountInfo<'b>,
+}
+
+struct AccessControlResponse {
+    voting_ends_at: i64,
+    /// outstanding shares the quorum is measured against
+    fund_shares: u64,
+}
+
+struct StateTransistionRequest<'a, 'c> {
+    proposal_acc: &'c mut Proposal,
+    fund: &'a Pubkey,
+    proposer: &'a Pubkey,
+    recipient: Pubkey,
+    amount: u64,
+    description_hash: [u8; 32],
+    voting_ends_at: i64,
+    fund_shares: u64,
+}
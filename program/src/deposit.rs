@@ -1,7 +1,10 @@
-use crate::access_control;
+use crate::{access_control, pda, receipt};
 use fund::{
     accounts::{
+        allocation::Allocation,
         fund::{Fund, FundType},
+        payback_claim::PaybackClaim,
+        position::Position,
         vault::TokenVault,
     },
     error::{FundError, FundErrorCode},
@@ -19,6 +22,7 @@ pub fn handler(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     amount: u64,
+    proof: Vec<[u8; 32]>,
 ) -> Result<(), FundError> {
     msg!("Handler: deposit");
 
@@ -30,24 +34,76 @@ pub fn handler(
     let fund_acc_info = next_account_info(acc_infos)?;
     let vault_authority_acc_info = next_account_info(acc_infos)?;
     let token_program_acc_info = next_account_info(acc_infos)?;
+    let clock_acc_info = next_account_info(acc_infos)?;
+
+    let position_acc_info = next_account_info(acc_infos)?;
+    let system_program_acc_info = next_account_info(acc_infos)?;
+    let rent_acc_info = next_account_info(acc_infos)?;
 
     let nft_mint_acc_info = acc_infos.next(); // optional
     let nft_token_acc_info = acc_infos.next(); //optional
-    let whitelist_acc_info = acc_infos.next(); // optional
+    let payback_claim_acc_info = acc_infos.next(); // optional
+    let allocation_acc_info = acc_infos.next(); // optional
+    let whitelist_acc_infos = access_control::whitelist_pages(acc_infos)?; // optional
 
-    access_control(AccessControlRequest {
+    let AccessControlResponse {
+        amount,
+        claim_nonce,
+        position_nonce,
+        now,
+    } = access_control(AccessControlRequest {
         program_id,
         amount,
         fund_acc_info,
-        depositor_acc_info,
         depositor_authority_acc_info,
         vault_acc_info,
         vault_authority_acc_info,
+        clock_acc_info,
         nft_mint_acc_info,
         nft_token_acc_info,
-        whitelist_acc_info,
+        payback_claim_acc_info,
+        position_acc_info,
+        allocation_acc_info,
+        whitelist_acc_infos: &whitelist_acc_infos,
+        proof: &proof,
     })?;
 
+    // first deposit of a share holder, create their payback claim
+    if let Some(nonce) = claim_nonce {
+        let payback_claim_acc_info =
+            payback_claim_acc_info.ok_or(FundErrorCode::InvalidPaybackClaim)?;
+        let rent = access_control::rent(rent_acc_info)?;
+        let space = PaybackClaim::default().size()?;
+        let signer_seeds =
+            PaybackClaim::signer_seeds(fund_acc_info.key, depositor_authority_acc_info.key, &nonce);
+        pda::create_account(
+            program_id,
+            depositor_authority_acc_info,
+            payback_claim_acc_info,
+            system_program_acc_info,
+            &rent,
+            space,
+            &signer_seeds,
+        )?;
+    }
+
+    // first deposit into the fund, create the position of the depositor
+    if let Some(nonce) = position_nonce {
+        let rent = access_control::rent(rent_acc_info)?;
+        let space = Position::default().size()?;
+        let signer_seeds =
+            Position::signer_seeds(fund_acc_info.key, depositor_authority_acc_info.key, &nonce);
+        pda::create_account(
+            program_id,
+            depositor_authority_acc_info,
+            position_acc_info,
+            system_program_acc_info,
+            &rent,
+            space,
+            &signer_seeds,
+        )?;
+    }
+
     Fund::unpack_mut(
         &mut fund_acc_info.try_borrow_mut_data()?,
         &mut |fund_acc: &mut Fund| {
@@ -62,7 +118,11 @@ pub fn handler(
                 token_program_acc_info,
                 nft_mint_acc_info,
                 nft_token_acc_info,
+                payback_claim_acc_info,
+                position_acc_info,
+                allocation_acc_info,
                 amount,
+                now,
             })
             .map_err(Into::into)
         },
@@ -71,64 +131,132 @@ pub fn handler(
     Ok(())
 }
 
-fn access_control(req: AccessControlRequest) -> Result<(), FundError> {
+fn access_control(req: AccessControlRequest) -> Result<AccessControlResponse, FundError> {
     let AccessControlRequest {
         program_id,
-        amount,
+        mut amount,
         fund_acc_info,
-        depositor_acc_info,
         depositor_authority_acc_info,
         vault_acc_info,
         vault_authority_acc_info,
+        clock_acc_info,
         nft_mint_acc_info,
         nft_token_acc_info,
-        whitelist_acc_info,
+        payback_claim_acc_info,
+        position_acc_info,
+        allocation_acc_info,
+        whitelist_acc_infos,
+        proof,
     } = req;
 
     if !depositor_authority_acc_info.is_signer {
         return Err(FundErrorCode::Unauthorized.into());
     }
+    let mut claim_nonce = None;
+    let position_nonce;
+    let now;
     {
         // let rent = access_control::rent(rent_acc_info)?;
         let fund = access_control::fund(fund_acc_info, program_id)?;
+        access_control::not_paused(&fund)?;
         let _ = access_control::vault_join(
             vault_acc_info,
             vault_authority_acc_info,
             fund_acc_info,
             program_id,
         )?;
-        let _ = access_control::check_balance(fund_acc_info, amount)?;
-        let _ = access_control::fund_open(fund_acc_info, program_id)?;
-        // check if the despoitor is part of the whitelist.
+        amount = access_control::check_balance(fund_acc_info, amount)?;
+        if fund.allocations {
+            let allocation = access_control::allocation(
+                allocation_acc_info.ok_or(FundErrorCode::InvalidAllocation)?,
+                fund_acc_info.key,
+                depositor_authority_acc_info.key,
+                program_id,
+            )?;
+            amount = access_control::check_allocation(&allocation, amount)?;
+        }
+        let _ = access_control::fund_open(fund_acc_info, program_id, clock_acc_info)?;
+        now = access_control::clock(clock_acc_info)?.unix_timestamp;
+        if let FundType::Campaign { deadline, .. } = fund.fund_type {
+            if now >= deadline {
+                return Err(FundErrorCode::CampaignEnded.into());
+            }
+        }
+        if fund.fund_type.mints_receipts() {
+            let _ = access_control::check_nft(
+                &fund,
+                nft_mint_acc_info.ok_or(FundErrorCode::NFTMintMissing)?,
+                nft_token_acc_info.ok_or(FundErrorCode::NFTTokenAccountMissing)?,
+            )?;
+        }
         if fund.fund_type.eq(&FundType::Raise { private: true })
             || fund.fund_type.eq(&FundType::Raise { private: false })
         {
-            let _ = access_control::check_nft(
+            // receipts stay with the depositor the claim is for
+            let _ = access_control::shares(
                 &fund,
-                nft_mint_acc_info
-                    .ok_or(FundErrorCode::NFTMintMissing)
-                    .unwrap(),
-                nft_token_acc_info
-                    .ok_or(FundErrorCode::NFTTokenAccountMissing)
-                    .unwrap(),
+                nft_token_acc_info.ok_or(FundErrorCode::NFTTokenAccountMissing)?,
+                depositor_authority_acc_info.key,
             )?;
+            let payback_claim_acc_info =
+                payback_claim_acc_info.ok_or(FundErrorCode::InvalidPaybackClaim)?;
+            if payback_claim_acc_info.owner == program_id {
+                let _ = access_control::payback_claim(
+                    payback_claim_acc_info,
+                    fund_acc_info.key,
+                    depositor_authority_acc_info.key,
+                    program_id,
+                )?;
+            } else {
+                let (payback_claim, nonce) = Pubkey::find_program_address(
+                    &PaybackClaim::seeds(fund_acc_info.key, depositor_authority_acc_info.key),
+                    program_id,
+                );
+                if payback_claim != *payback_claim_acc_info.key {
+                    return Err(FundErrorCode::InvalidPaybackClaim.into());
+                }
+                claim_nonce = Some(nonce);
+            }
         }
+        if position_acc_info.owner == program_id {
+            let _ = access_control::position(
+                position_acc_info,
+                fund_acc_info.key,
+                depositor_authority_acc_info.key,
+                program_id,
+            )?;
+            position_nonce = None;
+        } else {
+            let (position, nonce) = Pubkey::find_program_address(
+                &Position::seeds(fund_acc_info.key, depositor_authority_acc_info.key),
+                program_id,
+            );
+            if position != *position_acc_info.key {
+                return Err(FundErrorCode::InvalidPosition.into());
+            }
+            position_nonce = Some(nonce);
+        }
+        // check if the despoitor is part of the whitelist.
         if fund.fund_type.eq(&FundType::Raise { private: true }) {
-            let _ = access_control::check_depositor(
+            access_control::check_depositor(
                 program_id,
-                whitelist_acc_info
-                    .ok_or(FundErrorCode::NFTTokenAccountMissing)
-                    .unwrap()
-                    .clone(),
+                whitelist_acc_infos,
+                fund_acc_info.key,
                 &fund,
-                depositor_acc_info,
+                depositor_authority_acc_info,
+                proof,
             )?;
         }
     }
 
     msg!("access control deposit success");
 
-    Ok(())
+    Ok(AccessControlResponse {
+        amount,
+        claim_nonce,
+        position_nonce,
+        now,
+    })
 }
 fn state_transistion(req: StateTransistionRequest) -> Result<(), FundError> {
     let StateTransistionRequest {
@@ -142,13 +270,28 @@ fn state_transistion(req: StateTransistionRequest) -> Result<(), FundError> {
         token_program_acc_info,
         nft_mint_acc_info,
         nft_token_acc_info,
+        payback_claim_acc_info,
+        position_acc_info,
+        allocation_acc_info,
         amount,
+        now,
     } = req;
 
+    // shares of the depositor before this deposit
+    let mut held = 0;
     {
-        if fund_acc.fund_type.eq(&FundType::Raise { private: false })
-            || fund_acc.fund_type.eq(&FundType::Raise { private: true })
-        {
+        let raise = matches!(fund_acc.fund_type, FundType::Raise { .. });
+        if raise {
+            receipt::thaw(
+                fund_acc_info.key,
+                fund_acc.nonce,
+                nft_token_acc_info.unwrap(),
+                nft_mint_acc_info.unwrap(),
+                vault_authority_acc_info,
+                token_program_acc_info,
+            )?;
+        }
+        if fund_acc.fund_type.mints_receipts() {
             msg!("invoke SPL token mint");
             let mint_to_instr = instruction::mint_to(
                 &spl_token::ID,
@@ -163,9 +306,68 @@ fn state_transistion(req: StateTransistionRequest) -> Result<(), FundError> {
 
             program::invoke_signed(&mint_to_instr, &accounts[..], &[&signer_seeds])?;
         }
+        if raise {
+            receipt::freeze(
+                fund_acc_info.key,
+                fund_acc.nonce,
+                nft_token_acc_info.unwrap(),
+                nft_mint_acc_info.unwrap(),
+                vault_authority_acc_info,
+                token_program_acc_info,
+            )?;
+        }
+        if fund_acc.fund_type.eq(&FundType::Raise { private: false })
+            || fund_acc.fund_type.eq(&FundType::Raise { private: true })
+        {
+            // new shares only earn paybacks funded from now on
+            PaybackClaim::unpack_mut(
+                &mut payback_claim_acc_info.unwrap().try_borrow_mut_data()?,
+                &mut |claim_acc: &mut PaybackClaim| {
+                    held = claim_acc.shares;
+                    if !claim_acc.initialized {
+                        claim_acc.initialized = true;
+                        claim_acc.fund = *fund_acc_info.key;
+                        claim_acc.holder = *depositor_authority_acc_info.key;
+                    }
+                    claim_acc
+                        .add_shares(amount, fund_acc.payback_per_share)
+                        .map_err(Into::into)
+                },
+            )?;
+        }
     }
 
+    if fund_acc.allocations {
+        Allocation::unpack_mut(
+            &mut allocation_acc_info.unwrap().try_borrow_mut_data()?,
+            &mut |allocation_acc: &mut Allocation| {
+                allocation_acc.add(amount);
+                Ok(())
+            },
+        )?;
+    }
+
+    Position::unpack_mut(
+        &mut position_acc_info.try_borrow_mut_data()?,
+        &mut |position_acc: &mut Position| {
+            if !position_acc.initialized {
+                position_acc.open(*fund_acc_info.key, *depositor_authority_acc_info.key, held);
+            }
+            let shares = if fund_acc.fund_type.mints_receipts() {
+                amount
+            } else {
+                0
+            };
+            position_acc.deposit(amount, shares, now);
+            Ok(())
+        },
+    )?;
+
     fund_acc.add(amount);
+    if fund_acc.balance == fund_acc.max_balance {
+        fund_acc.open = false;
+        msg!("event: goal reached, fund closed");
+    }
     // Send tokens from depositor to fund account.
     // Now transfer SPL funds from the depositor, to the
     // program-controlled account.
@@ -199,13 +401,30 @@ struct AccessControlRequest<'a, 'b> {
     program_id: &'a Pubkey,
     amount: u64,
     fund_acc_info: &'a AccountInfo<'b>,
-    depositor_acc_info: &'a AccountInfo<'b>,
     depositor_authority_acc_info: &'a AccountInfo<'b>,
     vault_acc_info: &'a AccountInfo<'b>,
     vault_authority_acc_info: &'a AccountInfo<'b>,
+    clock_acc_info: &'a AccountInfo<'b>,
     nft_mint_acc_info: Option<&'a AccountInfo<'b>>,
     nft_token_acc_info: Option<&'a AccountInfo<'b>>,
-    whitelist_acc_info: Option<&'a AccountInfo<'b>>,
+    payback_claim_acc_info: Option<&'a AccountInfo<'b>>,
+    position_acc_info: &'a AccountInfo<'b>,
+    allocation_acc_info: Option<&'a AccountInfo<'b>>,
+    whitelist_acc_infos: &'a [&'a AccountInfo<'b>],
+    proof: &'a [[u8; 32]],
+}
+
+struct AccessControlResponse {
+    /// amount accepted by the fund
+    amount: u64,
+    /// nonce of the payback claim to create when a share holder deposits for
+    /// the first time
+    claim_nonce: Option<u8>,
+    /// nonce of the position to create when the depositor deposits for the
+    /// first time
+    position_nonce: Option<u8>,
+    /// unix timestamp of the deposit
+    now: i64,
 }
 
 struct StateTransistionRequest<'a, 'b, 'c> {
@@ -219,5 +438,9 @@ struct StateTransistionRequest<'a, 'b, 'c> {
     token_program_acc_info: &'a AccountInfo<'b>,
     nft_token_acc_info: Option<&'a AccountInfo<'b>>,
     nft_mint_acc_info: Option<&'a AccountInfo<'b>>,
+    payback_claim_acc_info: Option<&'a AccountInfo<'b>>,
+    position_acc_info: &'a AccountInfo<'b>,
+    allocation_acc_info: Option<&'a AccountInfo<'b>>,
     amount: u64,
+    now: i64,
 }
@@ -0,0 +1,168 @@
+use crate::access_control;
+use fund::{
+    accounts::{payback::Payback, Fund},
+    error::{FundError, FundErrorCode},
+};
+use serum_common::pack::Pack;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    msg, program,
+    pubkey::Pubkey,
+};
+use spl_token::instruction;
+use std::convert::Into;
+
+pub fn handler(program_id: &Pubkey, accounts: &[AccountInfo], round: u32) -> Result<(), FundError> {
+    msg!("handler: deposit_payback");
+
+    let acc_infos = &mut accounts.iter();
+
+    let fund_acc_info = next_account_info(acc_infos)?;
+    let owner_acc_info = next_account_info(acc_infos)?;
+    let payback_acc_info = next_account_info(acc_infos)?;
+    let source_acc_info = next_account_info(acc_infos)?;
+    let payback_vault_acc_info = next_account_info(acc_infos)?;
+    let token_program_acc_info = next_account_info(acc_infos)?;
+    let signer_acc_infos = acc_infos.as_slice();
+
+    access_control(AccessControlRequest {
+        program_id,
+        round,
+        fund_acc_info,
+        owner_acc_info,
+        payback_acc_info,
+        source_acc_info,
+        payback_vault_acc_info,
+        signer_acc_infos,
+    })?;
+
+    Payback::unpack_mut(
+        &mut payback_acc_info.try_borrow_mut_data()?,
+        &mut |payback_acc: &mut Payback| {
+            Fund::unpack_mut(
+                &mut fund_acc_info.try_borrow_mut_data()?,
+                &mut |fund_acc: &mut Fund| {
+                    state_transistion(StateTransistionRequest {
+                        fund_acc,
+                        payback_acc,
+                        owner_acc_info,
+                        source_acc_info,
+                        payback_vault_acc_info,
+                        token_program_acc_info,
+                        signer_acc_infos,
+                    })
+                    .map_err(Into::into)
+                },
+            )
+        },
+    )?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<(), FundError> {
+    msg!("access-control: deposit_payback");
+
+    let AccessControlRequest {
+        program_id,
+        round,
+        fund_acc_info,
+        owner_acc_info,
+        payback_acc_info,
+        source_acc_info,
+        payback_vault_acc_info,
+        signer_acc_infos,
+    } = req;
+
+    let _ =
+        access_control::check_owner(program_id, fund_acc_info, owner_acc_info, signer_acc_infos)?;
+    let fund = access_control::fund(fund_acc_info, program_id)?;
+    access_control::not_paused(&fund)?;
+
+    let payback = access_control::payback(payback_acc_info, fund_acc_info.key, program_id)?;
+    if payback.round != round {
+        return Err(FundErrorCode::InvalidPaybackRound.into());
+    }
+    if payback.funded {
+        return Err(FundErrorCode::PaybackAlreadyFunded.into());
+    }
+    if fund.shares == 0 {
+        return Err(FundErrorCode::NoShares.into());
+    }
+
+    if *payback_vault_acc_info.key != fund.payback_vault {
+        return Err(FundErrorCode::InvalidPaybackVault.into());
+    }
+    let source = access_control::token(source_acc_info)?;
+    if source.mint != fund.mint {
+        return Err(FundErrorCode::InvalidTokenAccountMint.into());
+    }
+
+    msg!("access-control: success");
+
+    Ok(())
+}
+
+fn state_transistion(req: StateTransistionRequest) -> Result<(), FundError> {
+    msg!("state-transition: deposit_payback");
+
+    let StateTransistionRequest {
+        fund_acc,
+        payback_acc,
+        owner_acc_info,
+        source_acc_info,
+        payback_vault_acc_info,
+        token_program_acc_info,
+        signer_acc_infos,
+    } = req;
+
+    let per_share = fund_acc.fund_payback(payback_acc.total)?;
+    payback_acc.mark_funded(per_share);
+
+    {
+        msg!("invoke SPL token transfer");
+        // a multisig owner signs the transfer through its signers
+        let signer_keys: Vec<&Pubkey> = signer_acc_infos.iter().map(|acc| acc.key).collect();
+        let deposit_instruction = instruction::transfer(
+            &spl_token::ID,
+            source_acc_info.key,
+            payback_vault_acc_info.key,
+            owner_acc_info.key,
+            &signer_keys,
+            payback_acc.total,
+        )?;
+        let mut accounts = vec![
+            source_acc_info.clone(),
+            owner_acc_info.clone(),
+            payback_vault_acc_info.clone(),
+            token_program_acc_info.clone(),
+        ];
+        accounts.extend_from_slice(signer_acc_infos);
+        program::invoke_signed(&deposit_instruction, &accounts[..], &[])?;
+    }
+
+    msg!("state-transition: success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a, 'b> {
+    program_id: &'a Pubkey,
+    round: u32,
+    fund_acc_info: &'a AccountInfo<'b>,
+    owner_acc_info: &'a AccountInfo<'b>,
+    payback_acc_info: &'a AccountInfo<'b>,
+    source_acc_info: &'a AccountInfo<'b>,
+    payback_vault_acc_info: &'a AccountInfo<'b>,
+    signer_acc_infos: &'a [AccountInfo<'b>],
+}
+
+struct StateTransistionRequest<'a, 'b, 'c> {
+    fund_acc: &'c mut Fund,
+    payback_acc: &'c mut Payback,
+    owner_acc_info: &'a AccountInfo<'b>,
+    source_acc_info: &'a AccountInfo<'b>,
+    payback_vault_acc_info: &'a AccountInfo<'b>,
+    token_program_acc_info: &'a AccountInfo<'b>,
+    signer_acc_infos: &'a [AccountInfo<'b>],
+}
@@ -0,0 +1,179 @@
+use crate::access_control;
+use fund::{
+    accounts::{
+        proposal::{Proposal, ProposalState},
+        vault::TokenVault,
+        Fund,
+    },
+    error::{FundError, FundErrorCode},
+};
+use serum_common::pack::Pack;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    msg, program,
+    pubkey::Pubkey,
+};
+use spl_token::instruction;
+use std::convert::Into;
+
+pub fn handler(program_id: &Pubkey, accounts: &[AccountInfo]) -> Result<(), FundError> {
+    msg!("handler: execute_proposal");
+
+    let acc_infos = &mut accounts.iter();
+
+    let proposal_acc_info = next_account_info(acc_infos)?;
+    let fund_acc_info = next_account_info(acc_infos)?;
+    let vault_acc_info = next_account_info(acc_infos)?;
+    let vault_authority_acc_info = next_account_info(acc_infos)?;
+    let recipient_acc_info = next_account_info(acc_infos)?;
+    let token_program_acc_info = next_account_info(acc_infos)?;
+    let clock_acc_info = next_account_info(acc_infos)?;
+
+    access_control(AccessControlRequest {
+        program_id,
+        proposal_acc_info,
+        fund_acc_info,
+        vault_acc_info,
+        vault_authority_acc_info,
+        recipient_acc_info,
+        clock_acc_info,
+    })?;
+
+    Proposal::unpack_mut(
+        &mut proposal_acc_info.try_borrow_mut_data()?,
+        &mut |proposal_acc: &mut Proposal| {
+            Fund::unpack_mut(
+                &mut fund_acc_info.try_borrow_mut_data()?,
+                &mut |fund_acc: &mut Fund| {
+                    state_transistion(StateTransistionRequest {
+                        proposal_acc,
+                        fund_acc,
+                        fund_acc_info,
+                        vault_acc_info,
+                        vault_authority_acc_info,
+                        recipient_acc_info,
+                        token_program_acc_info,
+                    })
+                    .map_err(Into::into)
+                },
+            )
+        },
+    )?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<(), FundError> {
+    msg!("access-control: execute_proposal");
+
+    let AccessControlRequest {
+        program_id,
+        proposal_acc_info,
+        fund_acc_info,
+        vault_acc_info,
+        vault_authority_acc_info,
+        recipient_acc_info,
+        clock_acc_info,
+    } = req;
+
+    let fund = access_control::fund(fund_acc_info, program_id)?;
+    access_control::not_paused(&fund)?;
+    let _ = access_control::vault_join(
+        vault_acc_info,
+        vault_authority_acc_info,
+        fund_acc_info,
+        program_id,
+    )?;
+    let proposal = access_control::proposal(proposal_acc_info, fund_acc_info.key, program_id)?;
+
+    if proposal.state != ProposalState::Succeeded {
+        return Err(FundErrorCode::ProposalNotPassed.into());
+    }
+    // give dissenting share holders time to exit
+    let clock = access_control::clock(clock_acc_info)?;
+    if clock.unix_timestamp < proposal.executable_at(&fund.governance)? {
+        return Err(FundErrorCode::ProposalTimelocked.into());
+    }
+    if proposal.amount > fund.balance {
+        return Err(FundErrorCode::InsufficientBalance.into());
+    }
+
+    // the recipient must be the token account voted on
+    let recipient = access_control::token(recipient_acc_info)?;
+    if proposal.recipient != *recipient_acc_info.key {
+        return Err(FundErrorCode::InvalidRecipient.into());
+    }
+    if recipient.mint != fund.mint {
+        return Err(FundErrorCode::InvalidTokenAccountMint.into());
+    }
+
+    msg!("access-control: success");
+
+    Ok(())
+}
+
+fn state_transistion(req: StateTransistionRequest) -> Result<(), FundError> {
+    msg!("state-transition: execute_proposal");
+
+    let StateTransistionRequest {
+        proposal_acc,
+        fund_acc,
+        fund_acc_info,
+        vault_acc_info,
+        vault_authority_acc_info,
+        recipient_acc_info,
+        token_program_acc_info,
+    } = req;
+
+    {
+        proposal_acc.execute();
+        fund_acc.deduct(proposal_acc.amount)?;
+
+        msg!("invoking token transfer");
+        let transfer_instruction = instruction::transfer(
+            &spl_token::ID,
+            vault_acc_info.key,
+            recipient_acc_info.key,
+            vault_authority_acc_info.key,
+            &[],
+            proposal_acc.amount,
+        )?;
+
+        let signer_seeds = TokenVault::signer_seeds(fund_acc_info.key, &fund_acc.nonce);
+
+        program::invoke_signed(
+            &transfer_instruction,
+            &[
+                vault_acc_info.clone(),
+                recipient_acc_info.clone(),
+                vault_authority_acc_info.clone(),
+                token_program_acc_info.clone(),
+            ],
+            &[&signer_seeds],
+        )?;
+    }
+
+    msg!("state-transition: success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a, 'b> {
+    program_id: &'a Pubkey,
+    proposal_acc_info: &'a AccountInfo<'b>,
+    fund_acc_info: &'a AccountInfo<'b>,
+    vault_acc_info: &'a AccountInfo<'b>,
+    vault_authority_acc_info: &'a AccountInfo<'b>,
+    recipient_acc_info: &'a AccountInfo<'b>,
+    clock_acc_info: &'a AccountInfo<'b>,
+}
+
+struct StateTransistionRequest<'a, 'b, 'c> {
+    proposal_acc: &'c mut Proposal,
+    fund_acc: &'c mut Fund,
+    fund_acc_info: &'a AccountInfo<'b>,
+    vault_acc_info: &'a AccountInfo<'b>,
+    vault_authority_acc_info: &'a AccountInfo<'b>,
+    recipient_acc_info: &'a AccountInfo<'b>,
+    token_program_acc_info: &'a AccountInfo<'b>,
+}
@@ -0,0 +1,107 @@
+use crate::access_control;
+use fund::{
+    accounts::{fund::Governance, proposal::Proposal},
+    error::{FundError, FundErrorCode},
+};
+use serum_common::pack::Pack;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    msg,
+    pubkey::Pubkey,
+};
+use std::convert::Into;
+
+pub fn handler(program_id: &Pubkey, accounts: &[AccountInfo]) -> Result<(), FundError> {
+    msg!("handler: finalize_proposal");
+
+    let acc_infos = &mut accounts.iter();
+
+    let proposal_acc_info = next_account_info(acc_infos)?;
+    let fund_acc_info = next_account_info(acc_infos)?;
+    let clock_acc_info = next_account_info(acc_infos)?;
+
+    let AccessControlResponse { governance, now } = access_control(AccessControlRequest {
+        program_id,
+        proposal_acc_info,
+        fund_acc_info,
+        clock_acc_info,
+    })?;
+
+    Proposal::unpack_mut(
+        &mut proposal_acc_info.try_borrow_mut_data()?,
+        &mut |proposal_acc: &mut Proposal| {
+            state_transistion(StateTransistionRequest {
+                proposal_acc,
+                governance: &governance,
+                now,
+            })
+            .map_err(Into::into)
+        },
+    )?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<AccessControlResponse, FundError> {
+    msg!("access-control: finalize_proposal");
+
+    let AccessControlRequest {
+        program_id,
+        proposal_acc_info,
+        fund_acc_info,
+        clock_acc_info,
+    } = req;
+
+    let fund = access_control::fund(fund_acc_info, program_id)?;
+    access_control::not_paused(&fund)?;
+    let proposal = access_control::proposal(proposal_acc_info, fund_acc_info.key, program_id)?;
+    let clock = access_control::clock(clock_acc_info)?;
+
+    if !proposal.is_active() {
+        return Err(FundErrorCode::ProposalNotActive.into());
+    }
+    if clock.unix_timestamp < proposal.voting_ends_at {
+        return Err(FundErrorCode::VotingInProgress.into());
+    }
+
+    msg!("access-control: success");
+
+    Ok(AccessControlResponse {
+        governance: fund.governance,
+        now: clock.unix_timestamp,
+    })
+}
+
+fn state_transistion(req: StateTransistionRequest) -> Result<(), FundError> {
+    msg!("state-transition: finalize_proposal");
+
+    let StateTransistionRequest {
+        proposal_acc,
+        governance,
+        now,
+    } = req;
+
+    proposal_acc.finalize(governance, now);
+
+    msg!("state-transition: success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a, 'b> {
+    program_id: &'a Pubkey,
+    proposal_acc_info: &'a AccountInfo<'b>,
+    fund_acc_info: &'a AccountInfo<'b>,
+    clock_acc_info: &'a AccountInfo<'b>,
+}
+
+struct AccessControlResponse {
+    governance: Governance,
+    now: i64,
+}
+
+struct StateTransistionRequest<'a, 'c> {
+    proposal_acc: &'c mut Proposal,
+    governance: &'a Governance,
+    now: i64,
+}
@@ -3,8 +3,9 @@
 use crate::access_control;
 use fund::{
     accounts::{
-        fund::{Fund, FundType},
+        fund::{Fund, FundType, Governance},
         vault::TokenVault,
+        Whitelist,
     },
     error::{FundError, FundErrorCode},
 };
@@ -18,6 +19,7 @@ use solana_program::{
 };
 use std::convert::Into;
 
+#[allow(clippy::too_many_arguments)]
 pub fn handler(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -25,6 +27,10 @@ pub fn handler(
     authority: Pubkey,
     max_balance: u64,
     fund_type: FundType,
+    governance: Governance,
+    opens_at: Option<i64>,
+    closes_at: Option<i64>,
+    guardian: Option<Pubkey>,
 ) -> Result<(), FundError> {
     msg!("Initialize Fund");
 
@@ -38,6 +44,7 @@ pub fn handler(
     let whitelist_acc_info = acc_infos.next();
     let nft_token_acc_info = acc_infos.next();
     let nft_mint_acc_info = acc_infos.next();
+    let payback_vault_acc_info = acc_infos.next();
 
     access_control(AccessControlRequest {
         program_id,
@@ -46,6 +53,13 @@ pub fn handler(
         vault_acc_info,
         rent_acc_info,
         nft_mint_acc_info,
+        payback_vault_acc_info,
+        whitelist_acc_info,
+        fund_type,
+        governance,
+        max_balance,
+        opens_at,
+        closes_at,
         nonce: 0,
     })?;
 
@@ -56,16 +70,22 @@ pub fn handler(
         &mut |fund_acc: &mut Fund| {
             state_transition(StateTransitionRequest {
                 fund_acc,
+                fund_addr: fund_acc_info.key,
                 owner,
                 authority,
                 mint: mint_acc_info.key,
                 nft_mint_acc_info,
                 nft_token_acc_info,
+                payback_vault_acc_info,
                 vault: *vault_acc_info.key,
                 whitelist_acc_info,
                 fund_type,
+                governance,
                 nonce: 0,
                 max_balance,
+                opens_at,
+                closes_at,
+                guardian,
             })
             .map_err(Into::into)
         },
@@ -83,7 +103,14 @@ fn access_control(req: AccessControlRequest) -> Result<(), FundError> {
         mint_acc_info,
         rent_acc_info,
         nft_mint_acc_info,
+        payback_vault_acc_info,
+        whitelist_acc_info,
         vault_acc_info,
+        fund_type,
+        governance,
+        max_balance,
+        opens_at,
+        closes_at,
         nonce,
     } = req;
 
@@ -114,18 +141,65 @@ fn access_control(req: AccessControlRequest) -> Result<(), FundError> {
         }
     }
 
-    if fund.fund_type.eq(&FundType::Raise { private: false })
-        || fund.fund_type.eq(&FundType::Raise { private: true })
-    {
+    if fund_type.mints_receipts() {
         let nft_mint = access_control::mint(nft_mint_acc_info.unwrap())?;
         let fund_authority = Pubkey::create_program_address(
-            &TokenVault::signer_seeds(&fund_acc_info.key, &fund.nonce),
+            &TokenVault::signer_seeds(&fund_acc_info.key, &nonce),
             program_id,
         )
         .map_err(|_| FundErrorCode::InvalidVaultNonce)?;
         if nft_mint.mint_authority != COption::Some(fund_authority) {
             return Err(FundErrorCode::InvalidMintAuthority.into());
         }
+        // receipts of a raise are frozen to their depositor
+        if matches!(fund_type, FundType::Raise { .. })
+            && nft_mint.freeze_authority != COption::Some(fund_authority)
+        {
+            return Err(FundErrorCode::InvalidFreezeAuthority.into());
+        }
+    }
+
+    if fund_type.eq(&FundType::Raise { private: false })
+        || fund_type.eq(&FundType::Raise { private: true })
+    {
+        if !governance.is_valid() {
+            return Err(FundErrorCode::InvalidGovernance.into());
+        }
+
+        let payback_vault_acc_info =
+            payback_vault_acc_info.ok_or(FundErrorCode::InvalidPaybackVault)?;
+        let payback_vault = access_control::token(payback_vault_acc_info)?;
+        let vault_authority = Pubkey::create_program_address(
+            &TokenVault::signer_seeds(fund_acc_info.key, &nonce),
+            program_id,
+        )
+        .map_err(|_| FundErrorCode::InvalidVaultNonce)?;
+        if payback_vault.owner != vault_authority
+            || payback_vault.mint != *mint_acc_info.key
+            || payback_vault_acc_info.key == vault_acc_info.key
+        {
+            return Err(FundErrorCode::InvalidPaybackVault.into());
+        }
+    }
+
+    if fund_type.eq(&FundType::Raise { private: true }) {
+        let whitelist_acc_info = whitelist_acc_info.ok_or(FundErrorCode::InvalidWhitelist)?;
+        if whitelist_acc_info.owner != program_id {
+            return Err(FundErrorCode::InvalidAccountOwner.into());
+        }
+        let _ = Whitelist::new(whitelist_acc_info.clone())?;
+    }
+
+    if let FundType::Campaign { goal, .. } = fund_type {
+        if goal == 0 || goal > max_balance {
+            return Err(FundErrorCode::InvalidCampaign.into());
+        }
+    }
+
+    if let (Some(opens_at), Some(closes_at)) = (opens_at, closes_at) {
+        if closes_at <= opens_at {
+            return Err(FundErrorCode::InvalidDepositWindow.into());
+        }
     }
 
     // Mint (initialized but not yet on Safe).
@@ -141,15 +215,21 @@ fn state_transition(req: StateTransitionRequest) -> Result<(), FundError> {
 
     let StateTransitionRequest {
         fund_acc,
+        fund_addr,
         owner,
         authority,
         vault,
         mint,
         nft_mint_acc_info,
         nft_token_acc_info,
+        payback_vault_acc_info,
         fund_type,
+        governance,
         nonce,
         max_balance,
+        opens_at,
+        closes_at,
+        guardian,
         whitelist_acc_info,
     } = req;
 
@@ -163,16 +243,25 @@ fn state_transition(req: StateTransitionRequest) -> Result<(), FundError> {
     fund_acc.balance = 0;
     fund_acc.fund_type = fund_type;
     fund_acc.nonce = nonce;
+    fund_acc.opens_at = opens_at.unwrap_or(0);
+    fund_acc.closes_at = closes_at.unwrap_or(0);
+    fund_acc.guardian = guardian.unwrap_or_default();
 
+    if fund_type.mints_receipts() {
+        fund_acc.nft_mint = *nft_mint_acc_info.unwrap().key;
+        fund_acc.nft_account = *nft_token_acc_info.unwrap().key;
+    }
     if fund_type.eq(&FundType::Raise { private: false })
         || fund_type.eq(&FundType::Raise { private: true })
     {
-        fund_acc.nft_mint = *nft_mint_acc_info.unwrap().key;
-        fund_acc.nft_account = *nft_token_acc_info.unwrap().key;
         fund_acc.round = 0u32;
+        fund_acc.governance = governance;
+        fund_acc.payback_vault = *payback_vault_acc_info.unwrap().key;
     }
     if fund_type.eq(&FundType::Raise { private: true }) {
-        fund_acc.whitelist = *whitelist_acc_info.unwrap().key;
+        let whitelist_acc_info = whitelist_acc_info.unwrap();
+        Whitelist::new(whitelist_acc_info.clone())?.init(fund_addr)?;
+        fund_acc.whitelist = *whitelist_acc_info.key;
     }
 
     msg!("state-transition: success");
@@ -186,20 +275,33 @@ struct AccessControlRequest<'a, 'b> {
     mint_acc_info: &'a AccountInfo<'b>,
     rent_acc_info: &'a AccountInfo<'b>,
     nft_mint_acc_info: Option<&'a AccountInfo<'b>>,
+    payback_vault_acc_info: Option<&'a AccountInfo<'b>>,
+    whitelist_acc_info: Option<&'a AccountInfo<'b>>,
     vault_acc_info: &'a AccountInfo<'b>,
+    fund_type: FundType,
+    governance: Governance,
+    max_balance: u64,
+    opens_at: Option<i64>,
+    closes_at: Option<i64>,
     nonce: u8,
 }
 
 struct StateTransitionRequest<'a, 'b> {
     fund_acc: &'a mut Fund,
+    fund_addr: &'a Pubkey,
     owner: Pubkey,
     mint: &'a Pubkey,
     whitelist_acc_info: Option<&'a AccountInfo<'b>>,
     nft_token_acc_info: Option<&'a AccountInfo<'b>>,
     nft_mint_acc_info: Option<&'a AccountInfo<'b>>,
+    payback_vault_acc_info: Option<&'a AccountInfo<'b>>,
     vault: Pubkey,
     authority: Pubkey,
     fund_type: FundType,
+    governance: Governance,
     nonce: u8,
     max_balance: u64,
+    opens_at: Option<i64>,
+    closes_at: Option<i64>,
+    guardian: Option<Pubkey>,
 }
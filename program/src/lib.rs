@@ -11,13 +11,33 @@ use solana_program::{
     account_info::AccountInfo, entrypoint, entrypoint::ProgramResult, msg, pubkey::Pubkey,
 };
 
+mod accept_ownership;
 pub(crate) mod access_control;
+mod cancel_proposal;
+mod cast_vote;
+mod claim_payback;
 mod close;
+mod create_proposal;
 mod deposit;
+mod deposit_payback;
+mod execute_proposal;
+mod finalize_proposal;
 mod initialize;
+mod pause;
+pub(crate) mod pda;
+mod propose_owner;
+mod rage_quit;
+mod receipt;
+mod refund;
 mod register_payback;
+mod reopen;
+mod set_allocation;
+mod set_guardian;
+mod set_whitelist_root;
+mod update_config;
 mod whitelist_add;
 mod whitelist_delete;
+mod whitelist_resize;
 mod withdraw;
 
 entrypoint!(process_instruction);
@@ -37,6 +57,10 @@ fn process_instruction(
             authority,
             max_balance,
             fund_type,
+            governance,
+            opens_at,
+            closes_at,
+            guardian,
         } => initialize::handler(
             program_id,
             accounts,
@@ -44,8 +68,14 @@ fn process_instruction(
             authority,
             max_balance,
             fund_type,
+            governance,
+            opens_at,
+            closes_at,
+            guardian,
         ),
-        FundInstruction::Deposit { amount } => deposit::handler(program_id, accounts, amount),
+        FundInstruction::Deposit { amount, proof } => {
+            deposit::handler(program_id, accounts, amount, proof)
+        }
         FundInstruction::Withdraw { amount } => withdraw::handler(program_id, accounts, amount),
         FundInstruction::Close => close::handler(program_id, accounts),
         FundInstruction::WhitelistAdd { entry } => {
@@ -57,6 +87,58 @@ fn process_instruction(
         FundInstruction::RegisterPayback { amount } => {
             register_payback::handler(program_id, accounts, amount)
         }
+        FundInstruction::DepositPayback { round } => {
+            deposit_payback::handler(program_id, accounts, round)
+        }
+        FundInstruction::ClaimPayback => claim_payback::handler(program_id, accounts),
+        FundInstruction::CreateProposal {
+            amount,
+            recipient,
+            description_hash,
+        } => create_proposal::handler(program_id, accounts, amount, recipient, description_hash),
+        FundInstruction::CancelProposal => cancel_proposal::handler(program_id, accounts),
+        FundInstruction::CastVote { vote } => cast_vote::handler(program_id, accounts, vote),
+        FundInstruction::FinalizeProposal => finalize_proposal::handler(program_id, accounts),
+        FundInstruction::ExecuteProposal => execute_proposal::handler(program_id, accounts),
+        FundInstruction::RageQuit { shares } => rage_quit::handler(program_id, accounts, shares),
+        FundInstruction::Refund { amount } => refund::handler(program_id, accounts, amount),
+        FundInstruction::Reopen {
+            new_max_balance,
+            new_closes_at,
+        } => reopen::handler(program_id, accounts, new_max_balance, new_closes_at),
+        FundInstruction::UpdateConfig {
+            max_balance,
+            private,
+            authority,
+            guardian,
+            allocations,
+        } => update_config::handler(
+            program_id,
+            accounts,
+            max_balance,
+            private,
+            authority,
+            guardian,
+            allocations,
+        ),
+        FundInstruction::ProposeOwner { new_owner } => {
+            propose_owner::handler(program_id, accounts, new_owner)
+        }
+        FundInstruction::AcceptOwnership => accept_ownership::handler(program_id, accounts),
+        FundInstruction::Pause => pause::handler(program_id, accounts, true),
+        FundInstruction::Unpause => pause::handler(program_id, accounts, false),
+        FundInstruction::WhitelistResize => whitelist_resize::handler(program_id, accounts),
+        FundInstruction::SetWhitelistRoot { root } => {
+            set_whitelist_root::handler(program_id, accounts, root)
+        }
+        FundInstruction::SetAllocation {
+            depositor,
+            min,
+            max,
+        } => set_allocation::handler(program_id, accounts, depositor, min, max),
+        FundInstruction::SetGuardian { guardian } => {
+            set_guardian::handler(program_id, accounts, guardian)
+        }
     };
 
     result?;
@@ -0,0 +1,106 @@
+use crate::access_control;
+use fund::{
+    accounts::fund::Fund,
+    error::{FundError, FundErrorCode},
+};
+use serum_common::pack::Pack;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    msg,
+    pubkey::Pubkey,
+};
+
+pub fn handler(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    paused: bool,
+) -> Result<(), FundError> {
+    msg!("handler: pause");
+    let acc_infos = &mut accounts.iter();
+
+    let fund_acc_info = next_account_info(acc_infos)?;
+    let guardian_acc_info = next_account_info(acc_infos)?;
+    let clock_acc_info = next_account_info(acc_infos)?;
+
+    let AccessControlResponse { now } = access_control(AccessControlRequest {
+        program_id,
+        fund_acc_info,
+        guardian_acc_info,
+        clock_acc_info,
+    })?;
+
+    Fund::unpack_mut(
+        &mut fund_acc_info.try_borrow_mut_data()?,
+        &mut |fund_acc: &mut Fund| {
+            state_transition(StateTransistionRequest {
+                fund_acc,
+                paused,
+                now,
+            })
+            .map_err(Into::into)
+        },
+    )?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<AccessControlResponse, FundError> {
+    let AccessControlRequest {
+        program_id,
+        fund_acc_info,
+        guardian_acc_info,
+        clock_acc_info,
+    } = req;
+
+    if !guardian_acc_info.is_signer {
+        return Err(FundErrorCode::Unauthorized.into());
+    }
+
+    let fund = access_control::fund(fund_acc_info, program_id)?;
+    if fund.guardian == Pubkey::default() || fund.guardian != *guardian_acc_info.key {
+        return Err(FundErrorCode::InvalidGuardian.into());
+    }
+
+    let now = access_control::clock(clock_acc_info)?.unix_timestamp;
+
+    msg!("access control pause success");
+
+    Ok(AccessControlResponse { now })
+}
+
+fn state_transition(req: StateTransistionRequest) -> Result<(), FundError> {
+    let StateTransistionRequest {
+        fund_acc,
+        paused,
+        now,
+    } = req;
+
+    if paused {
+        fund_acc.pause(now);
+        msg!("event: fund paused");
+    } else {
+        fund_acc.unpause();
+        msg!("event: fund unpaused");
+    }
+
+    msg!("state transition pause success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a, 'b> {
+    program_id: &'a Pubkey,
+    fund_acc_info: &'a AccountInfo<'b>,
+    guardian_acc_info: &'a AccountInfo<'b>,
+    clock_acc_info: &'a AccountInfo<'b>,
+}
+
+struct AccessControlResponse {
+    now: i64,
+}
+
+struct StateTransistionRequest<'c> {
+    fund_acc: &'c mut Fund,
+    paused: bool,
+    now: i64,
+}
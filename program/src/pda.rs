@@ -0,0 +1,49 @@
+use fund::error::FundError;
+use solana_program::{
+    account_info::AccountInfo, program, pubkey::Pubkey, system_instruction, sysvar::rent::Rent,
+};
+
+/// Creates a rent exempt account owned by the program at a program derived
+/// address, paid for by the payer. Anyone can send lamports to the address
+/// before it is created, so a funded address is topped up to rent exemption
+/// and allocated and assigned instead of created.
+pub fn create_account<'a>(
+    program_id: &Pubkey,
+    payer_acc_info: &AccountInfo<'a>,
+    acc_info: &AccountInfo<'a>,
+    system_program_acc_info: &AccountInfo<'a>,
+    rent: &Rent,
+    space: u64,
+    signer_seeds: &[&[u8]],
+) -> Result<(), FundError> {
+    let lamports = rent.minimum_balance(space as usize);
+    let acc_infos = &[
+        payer_acc_info.clone(),
+        acc_info.clone(),
+        system_program_acc_info.clone(),
+    ];
+
+    if acc_info.lamports() == 0 {
+        let create_instr = system_instruction::create_account(
+            payer_acc_info.key,
+            acc_info.key,
+            lamports,
+            space,
+            program_id,
+        );
+        program::invoke_signed(&create_instr, acc_infos, &[signer_seeds])?;
+        return Ok(());
+    }
+
+    let top_up = lamports.saturating_sub(acc_info.lamports());
+    if top_up > 0 {
+        let transfer_instr = system_instruction::transfer(payer_acc_info.key, acc_info.key, top_up);
+        program::invoke(&transfer_instr, acc_infos)?;
+    }
+    let allocate_instr = system_instruction::allocate(acc_info.key, space);
+    program::invoke_signed(&allocate_instr, acc_infos, &[signer_seeds])?;
+    let assign_instr = system_instruction::assign(acc_info.key, program_id);
+    program::invoke_signed(&assign_instr, acc_infos, &[signer_seeds])?;
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use crate::access_control;
+use fund::{
+    accounts::fund::Fund,
+    error::{FundError, FundErrorCode},
+};
+use serum_common::pack::Pack;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    msg,
+    pubkey::Pubkey,
+};
+
+pub fn handler(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_owner: Pubkey,
+) -> Result<(), FundError> {
+    msg!("handler: propose_owner");
+    let acc_infos = &mut accounts.iter();
+
+    let fund_acc_info = next_account_info(acc_infos)?;
+    let fund_owner_acc_info = next_account_info(acc_infos)?;
+    let signer_acc_infos = acc_infos.as_slice();
+
+    access_control(AccessControlRequest {
+        program_id,
+        fund_acc_info,
+        fund_owner_acc_info,
+        signer_acc_infos,
+        new_owner,
+    })?;
+
+    Fund::unpack_mut(
+        &mut fund_acc_info.try_borrow_mut_data()?,
+        &mut |fund_acc: &mut Fund| {
+            state_transition(StateTransistionRequest {
+                fund_acc,
+                new_owner,
+            })
+            .map_err(Into::into)
+        },
+    )?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<(), FundError> {
+    let AccessControlRequest {
+        program_id,
+        fund_acc_info,
+        fund_owner_acc_info,
+        signer_acc_infos,
+        new_owner,
+    } = req;
+
+    let _ = access_control::check_owner(
+        program_id,
+        fund_acc_info,
+        fund_owner_acc_info,
+        signer_acc_infos,
+    )?;
+    let fund = access_control::fund(fund_acc_info, program_id)?;
+    access_control::not_paused(&fund)?;
+
+    if new_owner == *fund_owner_acc_info.key {
+        return Err(FundErrorCode::InvalidPendingOwner.into());
+    }
+
+    msg!("access control propose_owner success");
+
+    Ok(())
+}
+
+fn state_transition(req: StateTransistionRequest) -> Result<(), FundError> {
+    let StateTransistionRequest {
+        fund_acc,
+        new_owner,
+    } = req;
+
+    fund_acc.propose_owner(new_owner);
+
+    msg!("state transition propose_owner success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a, 'b> {
+    program_id: &'a Pubkey,
+    fund_acc_info: &'a AccountInfo<'b>,
+    fund_owner_acc_info: &'a AccountInfo<'b>,
+    signer_acc_infos: &'a [AccountInfo<'b>],
+    new_owner: Pubkey,
+}
+
+struct StateTransistionRequest<'c> {
+    fund_acc: &'c mut Fund,
+    new_owner: Pubkey,
+}
@@ -0,0 +1,366 @@
+use crate::{access_control, receipt};
+use fund::{
+    accounts::{
+        fund::{Fund, FundType},
+        payback_claim::PaybackClaim,
+        position::Position,
+        proposal::Proposal,
+        vault::TokenVault,
+        vote_record::VoteRecord,
+    },
+    error::{FundError, FundErrorCode},
+};
+use serum_common::pack::Pack;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    msg, program,
+    pubkey::Pubkey,
+};
+use spl_token::instruction;
+use std::convert::Into;
+
+pub fn handler(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    shares: u64,
+) -> Result<(), FundError> {
+    msg!("handler: rage_quit");
+
+    let acc_infos = &mut accounts.iter();
+
+    let fund_acc_info = next_account_info(acc_infos)?;
+    let vault_acc_info = next_account_info(acc_infos)?;
+    let vault_authority_acc_info = next_account_info(acc_infos)?;
+    let holder_authority_acc_info = next_account_info(acc_infos)?;
+    let holder_token_acc_info = next_account_info(acc_infos)?;
+    let nft_mint_acc_info = next_account_info(acc_infos)?;
+    let destination_acc_info = next_account_info(acc_infos)?;
+    let token_program_acc_info = next_account_info(acc_infos)?;
+    let payback_claim_acc_info = next_account_info(acc_infos)?;
+    let clock_acc_info = next_account_info(acc_infos)?;
+    let position_acc_info = next_account_info(acc_infos)?;
+    let vote_acc_infos = acc_infos.as_slice();
+
+    let AccessControlResponse {
+        amount,
+        remaining,
+        reversals,
+        has_position,
+    } = access_control(AccessControlRequest {
+        program_id,
+        shares,
+        fund_acc_info,
+        vault_acc_info,
+        vault_authority_acc_info,
+        holder_authority_acc_info,
+        holder_token_acc_info,
+        nft_mint_acc_info,
+        destination_acc_info,
+        payback_claim_acc_info,
+        clock_acc_info,
+        position_acc_info,
+        vote_acc_infos,
+    })?;
+
+    // votes still open lose the burned shares
+    for idx in reversals {
+        let proposal_acc_info = &vote_acc_infos[idx * 2];
+        let vote_record_acc_info = &vote_acc_infos[idx * 2 + 1];
+        VoteRecord::unpack_mut(
+            &mut vote_record_acc_info.try_borrow_mut_data()?,
+            &mut |vote_record_acc: &mut VoteRecord| {
+                let vote = vote_record_acc.vote;
+                let weight = vote_record_acc.weight - remaining;
+                vote_record_acc.weight = remaining;
+                Proposal::unpack_mut(
+                    &mut proposal_acc_info.try_borrow_mut_data()?,
+                    &mut |proposal_acc: &mut Proposal| {
+                        proposal_acc.untally(vote, weight).map_err(Into::into)
+                    },
+                )
+            },
+        )?;
+    }
+
+    Fund::unpack_mut(
+        &mut fund_acc_info.try_borrow_mut_data()?,
+        &mut |fund_acc: &mut Fund| {
+            state_transistion(StateTransistionRequest {
+                fund_acc,
+                fund_acc_info,
+                vault_acc_info,
+                vault_authority_acc_info,
+                holder_authority_acc_info,
+                holder_token_acc_info,
+                nft_mint_acc_info,
+                destination_acc_info,
+                token_program_acc_info,
+                payback_claim_acc_info,
+                position_acc_info,
+                has_position,
+                shares,
+                amount,
+            })
+            .map_err(Into::into)
+        },
+    )?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<AccessControlResponse, FundError> {
+    msg!("access-control: rage_quit");
+
+    let AccessControlRequest {
+        program_id,
+        shares,
+        fund_acc_info,
+        vault_acc_info,
+        vault_authority_acc_info,
+        holder_authority_acc_info,
+        holder_token_acc_info,
+        nft_mint_acc_info,
+        destination_acc_info,
+        payback_claim_acc_info,
+        clock_acc_info,
+        position_acc_info,
+        vote_acc_infos,
+    } = req;
+
+    if !holder_authority_acc_info.is_signer {
+        return Err(FundErrorCode::Unauthorized.into());
+    }
+
+    let fund = access_control::fund(fund_acc_info, program_id)?;
+    if !fund.fund_type.eq(&FundType::Raise { private: true })
+        && !fund.fund_type.eq(&FundType::Raise { private: false })
+    {
+        return Err(FundErrorCode::InvalidFund.into());
+    }
+    let vault = access_control::vault_join(
+        vault_acc_info,
+        vault_authority_acc_info,
+        fund_acc_info,
+        program_id,
+    )?;
+
+    let held = access_control::shares(&fund, holder_token_acc_info, holder_authority_acc_info.key)?;
+    let claim = access_control::payback_claim(
+        payback_claim_acc_info,
+        fund_acc_info.key,
+        holder_authority_acc_info.key,
+        program_id,
+    )?;
+    if shares == 0 || shares > held || shares > claim.shares || shares > fund.shares {
+        return Err(FundErrorCode::InsufficientShares.into());
+    }
+    let has_position = access_control::position_if_any(
+        position_acc_info,
+        fund_acc_info.key,
+        holder_authority_acc_info.key,
+        program_id,
+    )?
+    .is_some();
+    if *nft_mint_acc_info.key != fund.nft_mint {
+        return Err(FundErrorCode::InvalidMint.into());
+    }
+
+    let destination = access_control::token(destination_acc_info)?;
+    if destination.mint != fund.mint {
+        return Err(FundErrorCode::InvalidTokenAccountMint.into());
+    }
+
+    // every vote counted on the claim has to be passed while any is open, votes
+    // backed by more than the shares left are reversed down to them
+    let remaining = claim.shares - shares;
+    let mut reversals = vec![];
+    let clock = access_control::clock(clock_acc_info)?;
+    if claim.voting(clock.unix_timestamp) {
+        if vote_acc_infos.len() != claim.open_votes as usize * 2 {
+            return Err(FundErrorCode::MissingVoteRecords.into());
+        }
+        for (idx, pair) in vote_acc_infos.chunks(2).enumerate() {
+            let proposal = access_control::proposal(&pair[0], fund_acc_info.key, program_id)?;
+            let vote_record = access_control::vote_record(
+                &pair[1],
+                pair[0].key,
+                holder_authority_acc_info.key,
+                program_id,
+            )?;
+            let duplicate = vote_acc_infos[..idx * 2]
+                .chunks(2)
+                .any(|seen| seen[1].key == pair[1].key);
+            if duplicate || vote_record.cast_at < claim.votes_since {
+                return Err(FundErrorCode::MissingVoteRecords.into());
+            }
+            if proposal.is_active()
+                && clock.unix_timestamp < proposal.voting_ends_at
+                && vote_record.weight > remaining
+            {
+                reversals.push(idx);
+            }
+        }
+    }
+
+    // pro-rata slice of the vault
+    let amount = (shares as u128 * vault.amount as u128 / fund.shares as u128) as u64;
+
+    msg!("access-control: success");
+
+    Ok(AccessControlResponse {
+        amount,
+        remaining,
+        reversals,
+        has_position,
+    })
+}
+
+fn state_transistion(req: StateTransistionRequest) -> Result<(), FundError> {
+    msg!("state-transition: rage_quit");
+
+    let StateTransistionRequest {
+        fund_acc,
+        fund_acc_info,
+        vault_acc_info,
+        vault_authority_acc_info,
+        holder_authority_acc_info,
+        holder_token_acc_info,
+        nft_mint_acc_info,
+        destination_acc_info,
+        token_program_acc_info,
+        payback_claim_acc_info,
+        position_acc_info,
+        has_position,
+        shares,
+        amount,
+    } = req;
+
+    fund_acc.rage_quit(shares, amount)?;
+    PaybackClaim::unpack_mut(
+        &mut payback_claim_acc_info.try_borrow_mut_data()?,
+        &mut |claim_acc: &mut PaybackClaim| {
+            claim_acc
+                .remove_shares(shares, fund_acc.payback_per_share)
+                .map_err(Into::into)
+        },
+    )?;
+    if has_position {
+        Position::unpack_mut(
+            &mut position_acc_info.try_borrow_mut_data()?,
+            &mut |position_acc: &mut Position| {
+                position_acc.rage_quit(shares, amount).map_err(Into::into)
+            },
+        )?;
+    }
+
+    receipt::thaw(
+        fund_acc_info.key,
+        fund_acc.nonce,
+        holder_token_acc_info,
+        nft_mint_acc_info,
+        vault_authority_acc_info,
+        token_program_acc_info,
+    )?;
+    {
+        msg!("invoke SPL token burn");
+        let burn_instruction = instruction::burn(
+            &spl_token::ID,
+            holder_token_acc_info.key,
+            nft_mint_acc_info.key,
+            holder_authority_acc_info.key,
+            &[],
+            shares,
+        )?;
+        program::invoke_signed(
+            &burn_instruction,
+            &[
+                holder_token_acc_info.clone(),
+                nft_mint_acc_info.clone(),
+                holder_authority_acc_info.clone(),
+                token_program_acc_info.clone(),
+            ],
+            &[],
+        )?;
+    }
+
+    receipt::freeze(
+        fund_acc_info.key,
+        fund_acc.nonce,
+        holder_token_acc_info,
+        nft_mint_acc_info,
+        vault_authority_acc_info,
+        token_program_acc_info,
+    )?;
+
+    {
+        msg!("invoke SPL token transfer");
+        let transfer_instruction = instruction::transfer(
+            &spl_token::ID,
+            vault_acc_info.key,
+            destination_acc_info.key,
+            vault_authority_acc_info.key,
+            &[],
+            amount,
+        )?;
+
+        let signer_seeds = TokenVault::signer_seeds(fund_acc_info.key, &fund_acc.nonce);
+
+        program::invoke_signed(
+            &transfer_instruction,
+            &[
+                vault_acc_info.clone(),
+                destination_acc_info.clone(),
+                vault_authority_acc_info.clone(),
+                token_program_acc_info.clone(),
+            ],
+            &[&signer_seeds],
+        )?;
+    }
+
+    msg!("state-transition: success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a, 'b> {
+    program_id: &'a Pubkey,
+    shares: u64,
+    fund_acc_info: &'a AccountInfo<'b>,
+    vault_acc_info: &'a AccountInfo<'b>,
+    vault_authority_acc_info: &'a AccountInfo<'b>,
+    holder_authority_acc_info: &'a AccountInfo<'b>,
+    holder_token_acc_info: &'a AccountInfo<'b>,
+    nft_mint_acc_info: &'a AccountInfo<'b>,
+    destination_acc_info: &'a AccountInfo<'b>,
+    payback_claim_acc_info: &'a AccountInfo<'b>,
+    clock_acc_info: &'a AccountInfo<'b>,
+    position_acc_info: &'a AccountInfo<'b>,
+    vote_acc_infos: &'a [AccountInfo<'b>],
+}
+
+struct AccessControlResponse {
+    amount: u64,
+    /// shares the claim is left with
+    remaining: u64,
+    /// open votes to reverse, by index of their pair of accounts
+    reversals: Vec<usize>,
+    /// false for holders without a position, whose shares were all bought
+    has_position: bool,
+}
+
+struct StateTransistionRequest<'a, 'b, 'c> {
+    fund_acc: &'c mut Fund,
+    fund_acc_info: &'a AccountInfo<'b>,
+    vault_acc_info: &'a AccountInfo<'b>,
+    vault_authority_acc_info: &'a AccountInfo<'b>,
+    holder_authority_acc_info: &'a AccountInfo<'b>,
+    holder_token_acc_info: &'a AccountInfo<'b>,
+    nft_mint_acc_info: &'a AccountInfo<'b>,
+    destination_acc_info: &'a AccountInfo<'b>,
+    token_program_acc_info: &'a AccountInfo<'b>,
+    payback_claim_acc_info: &'a AccountInfo<'b>,
+    position_acc_info: &'a AccountInfo<'b>,
+    has_position: bool,
+    shares: u64,
+    amount: u64,
+}
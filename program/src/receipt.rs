@@ -0,0 +1,74 @@
+use fund::{accounts::vault::TokenVault, error::FundError};
+use solana_program::{
+    account_info::AccountInfo, msg, program, program_pack::Pack as TokenPack, pubkey::Pubkey,
+};
+use spl_token::{instruction, state::Account as TokenAccount};
+
+/// Receipts of a raise stay frozen in the token account of the depositor, the
+/// shares on their payback claim always match the receipts they hold. The
+/// account is thawed only for the program to mint or burn receipts.
+pub fn thaw<'a>(
+    fund_addr: &Pubkey,
+    nonce: u8,
+    token_acc_info: &AccountInfo<'a>,
+    mint_acc_info: &AccountInfo<'a>,
+    vault_authority_acc_info: &AccountInfo<'a>,
+    token_program_acc_info: &AccountInfo<'a>,
+) -> Result<(), FundError> {
+    if !TokenAccount::unpack(&token_acc_info.try_borrow_data()?)?.is_frozen() {
+        return Ok(());
+    }
+
+    msg!("invoke SPL token thaw");
+    let thaw_instruction = instruction::thaw_account(
+        &spl_token::ID,
+        token_acc_info.key,
+        mint_acc_info.key,
+        vault_authority_acc_info.key,
+        &[],
+    )?;
+    let signer_seeds = TokenVault::signer_seeds(fund_addr, &nonce);
+    program::invoke_signed(
+        &thaw_instruction,
+        &[
+            token_acc_info.clone(),
+            mint_acc_info.clone(),
+            vault_authority_acc_info.clone(),
+            token_program_acc_info.clone(),
+        ],
+        &[&signer_seeds],
+    )?;
+
+    Ok(())
+}
+
+pub fn freeze<'a>(
+    fund_addr: &Pubkey,
+    nonce: u8,
+    token_acc_info: &AccountInfo<'a>,
+    mint_acc_info: &AccountInfo<'a>,
+    vault_authority_acc_info: &AccountInfo<'a>,
+    token_program_acc_info: &AccountInfo<'a>,
+) -> Result<(), FundError> {
+    msg!("invoke SPL token freeze");
+    let freeze_instruction = instruction::freeze_account(
+        &spl_token::ID,
+        token_acc_info.key,
+        mint_acc_info.key,
+        vault_authority_acc_info.key,
+        &[],
+    )?;
+    let signer_seeds = TokenVault::signer_seeds(fund_addr, &nonce);
+    program::invoke_signed(
+        &freeze_instruction,
+        &[
+            token_acc_info.clone(),
+            mint_acc_info.clone(),
+            vault_authority_acc_info.clone(),
+            token_program_acc_info.clone(),
+        ],
+        &[&signer_seeds],
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,257 @@
+use crate::access_control;
+use fund::{
+    accounts::{
+        fund::{Fund, FundType},
+        position::Position,
+        vault::TokenVault,
+    },
+    error::{FundError, FundErrorCode},
+};
+use serum_common::pack::Pack;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    msg, program,
+    pubkey::Pubkey,
+};
+use spl_token::instruction;
+use std::convert::Into;
+
+pub fn handler(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> Result<(), FundError> {
+    msg!("handler: refund");
+
+    let acc_infos = &mut accounts.iter();
+
+    let fund_acc_info = next_account_info(acc_infos)?;
+    let vault_acc_info = next_account_info(acc_infos)?;
+    let vault_authority_acc_info = next_account_info(acc_infos)?;
+    let depositor_authority_acc_info = next_account_info(acc_infos)?;
+    let depositor_token_acc_info = next_account_info(acc_infos)?;
+    let nft_mint_acc_info = next_account_info(acc_infos)?;
+    let destination_acc_info = next_account_info(acc_infos)?;
+    let token_program_acc_info = next_account_info(acc_infos)?;
+    let clock_acc_info = next_account_info(acc_infos)?;
+    let position_acc_info = next_account_info(acc_infos)?;
+
+    let AccessControlResponse { has_position } = access_control(AccessControlRequest {
+        program_id,
+        amount,
+        fund_acc_info,
+        vault_acc_info,
+        vault_authority_acc_info,
+        depositor_authority_acc_info,
+        depositor_token_acc_info,
+        nft_mint_acc_info,
+        destination_acc_info,
+        clock_acc_info,
+        position_acc_info,
+    })?;
+
+    Fund::unpack_mut(
+        &mut fund_acc_info.try_borrow_mut_data()?,
+        &mut |fund_acc: &mut Fund| {
+            state_transistion(StateTransistionRequest {
+                fund_acc,
+                fund_acc_info,
+                vault_acc_info,
+                vault_authority_acc_info,
+                depositor_authority_acc_info,
+                depositor_token_acc_info,
+                nft_mint_acc_info,
+                destination_acc_info,
+                token_program_acc_info,
+                position_acc_info,
+                has_position,
+                amount,
+            })
+            .map_err(Into::into)
+        },
+    )?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<AccessControlResponse, FundError> {
+    msg!("access-control: refund");
+
+    let AccessControlRequest {
+        program_id,
+        amount,
+        fund_acc_info,
+        vault_acc_info,
+        vault_authority_acc_info,
+        depositor_authority_acc_info,
+        depositor_token_acc_info,
+        nft_mint_acc_info,
+        destination_acc_info,
+        clock_acc_info,
+        position_acc_info,
+    } = req;
+
+    if !depositor_authority_acc_info.is_signer {
+        return Err(FundErrorCode::Unauthorized.into());
+    }
+
+    let fund = access_control::fund(fund_acc_info, program_id)?;
+    let deadline = match fund.fund_type {
+        FundType::Campaign { deadline, .. } => deadline,
+        _ => return Err(FundErrorCode::InvalidFund.into()),
+    };
+    let clock = access_control::clock(clock_acc_info)?;
+    if clock.unix_timestamp < deadline {
+        return Err(FundErrorCode::CampaignActive.into());
+    }
+    if fund.goal_reached() {
+        return Err(FundErrorCode::GoalReached.into());
+    }
+
+    let _ = access_control::vault_join(
+        vault_acc_info,
+        vault_authority_acc_info,
+        fund_acc_info,
+        program_id,
+    )?;
+
+    let held = access_control::shares(
+        &fund,
+        depositor_token_acc_info,
+        depositor_authority_acc_info.key,
+    )?;
+    if amount == 0 || amount > held || amount > fund.shares {
+        return Err(FundErrorCode::InsufficientShares.into());
+    }
+    let has_position = access_control::position_if_any(
+        position_acc_info,
+        fund_acc_info.key,
+        depositor_authority_acc_info.key,
+        program_id,
+    )?
+    .is_some();
+    if *nft_mint_acc_info.key != fund.nft_mint {
+        return Err(FundErrorCode::InvalidMint.into());
+    }
+
+    let destination = access_control::token(destination_acc_info)?;
+    if destination.mint != fund.mint {
+        return Err(FundErrorCode::InvalidTokenAccountMint.into());
+    }
+
+    msg!("access-control: success");
+
+    Ok(AccessControlResponse { has_position })
+}
+
+fn state_transistion(req: StateTransistionRequest) -> Result<(), FundError> {
+    msg!("state-transition: refund");
+
+    let StateTransistionRequest {
+        fund_acc,
+        fund_acc_info,
+        vault_acc_info,
+        vault_authority_acc_info,
+        depositor_authority_acc_info,
+        depositor_token_acc_info,
+        nft_mint_acc_info,
+        destination_acc_info,
+        token_program_acc_info,
+        position_acc_info,
+        has_position,
+        amount,
+    } = req;
+
+    fund_acc.refund(amount)?;
+    if has_position {
+        Position::unpack_mut(
+            &mut position_acc_info.try_borrow_mut_data()?,
+            &mut |position_acc: &mut Position| position_acc.refund(amount).map_err(Into::into),
+        )?;
+    }
+
+    {
+        msg!("invoke SPL token burn");
+        let burn_instruction = instruction::burn(
+            &spl_token::ID,
+            depositor_token_acc_info.key,
+            nft_mint_acc_info.key,
+            depositor_authority_acc_info.key,
+            &[],
+            amount,
+        )?;
+        program::invoke_signed(
+            &burn_instruction,
+            &[
+                depositor_token_acc_info.clone(),
+                nft_mint_acc_info.clone(),
+                depositor_authority_acc_info.clone(),
+                token_program_acc_info.clone(),
+            ],
+            &[],
+        )?;
+    }
+
+    {
+        msg!("invoke SPL token transfer");
+        let transfer_instruction = instruction::transfer(
+            &spl_token::ID,
+            vault_acc_info.key,
+            destination_acc_info.key,
+            vault_authority_acc_info.key,
+            &[],
+            amount,
+        )?;
+
+        let signer_seeds = TokenVault::signer_seeds(fund_acc_info.key, &fund_acc.nonce);
+
+        program::invoke_signed(
+            &transfer_instruction,
+            &[
+                vault_acc_info.clone(),
+                destination_acc_info.clone(),
+                vault_authority_acc_info.clone(),
+                token_program_acc_info.clone(),
+            ],
+            &[&signer_seeds],
+        )?;
+    }
+
+    msg!("state-transition: success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a, 'b> {
+    program_id: &'a Pubkey,
+    amount: u64,
+    fund_acc_info: &'a AccountInfo<'b>,
+    vault_acc_info: &'a AccountInfo<'b>,
+    vault_authority_acc_info: &'a AccountInfo<'b>,
+    depositor_authority_acc_info: &'a AccountInfo<'b>,
+    depositor_token_acc_info: &'a AccountInfo<'b>,
+    nft_mint_acc_info: &'a AccountInfo<'b>,
+    destination_acc_info: &'a AccountInfo<'b>,
+    clock_acc_info: &'a AccountInfo<'b>,
+    position_acc_info: &'a AccountInfo<'b>,
+}
+
+struct AccessControlResponse {
+    /// false for depositors without a position, whose receipts were all bought
+    has_position: bool,
+}
+
+struct StateTransistionRequest<'a, 'b, 'c> {
+    fund_acc: &'c mut Fund,
+    fund_acc_info: &'a AccountInfo<'b>,
+    vault_acc_info: &'a AccountInfo<'b>,
+    vault_authority_acc_info: &'a AccountInfo<'b>,
+    depositor_authority_acc_info: &'a AccountInfo<'b>,
+    depositor_token_acc_info: &'a AccountInfo<'b>,
+    nft_mint_acc_info: &'a AccountInfo<'b>,
+    destination_acc_info: &'a AccountInfo<'b>,
+    token_program_acc_info: &'a AccountInfo<'b>,
+    position_acc_info: &'a AccountInfo<'b>,
+    has_position: bool,
+    amount: u64,
+}
@@ -1,6 +1,6 @@
-use crate::access_control;
+use crate::{access_control, pda};
 use fund::{
-    accounts::Fund,
+    accounts::{payback::Payback, Fund},
     error::{FundError, FundErrorCode},
 };
 use serum_common::pack::Pack;
@@ -21,48 +21,98 @@ pub fn handler(
 
     let fund_acc_info = next_account_info(acc_infos)?;
     let owner_acc_info = next_account_info(acc_infos)?;
+    let payer_acc_info = next_account_info(acc_infos)?;
+    let payback_acc_info = next_account_info(acc_infos)?;
+    let system_program_acc_info = next_account_info(acc_infos)?;
+    let rent_acc_info = next_account_info(acc_infos)?;
+    let signer_acc_infos = acc_infos.as_slice();
 
-    access_control(AccessControlRequest {
+    let AccessControlResponse { round, nonce } = access_control(AccessControlRequest {
         program_id,
         fund_acc_info,
         owner_acc_info,
+        payer_acc_info,
+        payback_acc_info,
+        signer_acc_infos,
     })?;
 
+    // every round lives in its own account
+    {
+        let rent = access_control::rent(rent_acc_info)?;
+        let space = Payback::default().size()?;
+        let signer_seeds = Payback::signer_seeds(fund_acc_info.key, &round, &nonce);
+        pda::create_account(
+            program_id,
+            payer_acc_info,
+            payback_acc_info,
+            system_program_acc_info,
+            &rent,
+            space,
+            &signer_seeds,
+        )?;
+    }
+
+    Payback::unpack_mut(
+        &mut payback_acc_info.try_borrow_mut_data()?,
+        &mut |payback_acc: &mut Payback| {
+            payback_acc.initialized = true;
+            payback_acc.fund = *fund_acc_info.key;
+            payback_acc.round = round;
+            payback_acc.total = amount;
+            Ok(())
+        },
+    )?;
+
     Fund::unpack_mut(
         &mut fund_acc_info.try_borrow_mut_data()?,
         &mut |fund_acc: &mut Fund| {
-            state_transistion(StateTransistionRequest { fund_acc, amount }).map_err(Into::into)
+            state_transistion(StateTransistionRequest { fund_acc }).map_err(Into::into)
         },
     )?;
 
     Ok(())
 }
 
-fn access_control(req: AccessControlRequest) -> Result<(), FundError> {
+fn access_control(req: AccessControlRequest) -> Result<AccessControlResponse, FundError> {
     let AccessControlRequest {
         program_id,
         fund_acc_info,
         owner_acc_info,
+        payer_acc_info,
+        payback_acc_info,
+        signer_acc_infos,
     } = req;
 
-    if !owner_acc_info.is_signer {
+    let _ = access_control::check_authority(
+        program_id,
+        fund_acc_info,
+        owner_acc_info,
+        signer_acc_infos,
+    )?;
+    if !payer_acc_info.is_signer {
         return Err(FundErrorCode::Unauthorized.into());
     }
+    let fund = access_control::fund(fund_acc_info, program_id)?;
+    access_control::not_paused(&fund)?;
 
-    let _ = access_control::fund(fund_acc_info, program_id)?;
-
-    let _ = access_control::withdraw(program_id, fund_acc_info, owner_acc_info);
+    let (payback, nonce) =
+        Pubkey::find_program_address(&Payback::seeds(fund_acc_info.key, &fund.round), program_id);
+    if payback != *payback_acc_info.key {
+        return Err(FundErrorCode::InvalidPaybackRound.into());
+    }
 
-    Ok(())
+    Ok(AccessControlResponse {
+        round: fund.round,
+        nonce,
+    })
 }
 
 fn state_transistion(req: StateTransistionRequest) -> Result<(), FundError> {
-    let StateTransistionRequest { fund_acc, amount } = req;
+    let StateTransistionRequest { fund_acc } = req;
 
     msg!("State-Transistion: Initialize Register Payback");
 
-    let per_share = fund_acc.shares.checked_div(amount).unwrap();
-    fund_acc.add_new_payback(amount, per_share);
+    fund_acc.add_new_payback();
 
     msg!("State-Transistion: Initialize Register Payback Success");
     Ok(())
@@ -72,9 +122,16 @@ struct AccessControlRequest<'a, 'b> {
     program_id: &'a Pubkey,
     fund_acc_info: &'a AccountInfo<'b>,
     owner_acc_info: &'a AccountInfo<'b>,
+    payer_acc_info: &'a AccountInfo<'b>,
+    payback_acc_info: &'a AccountInfo<'b>,
+    signer_acc_infos: &'a [AccountInfo<'b>],
+}
+
+struct AccessControlResponse {
+    round: u32,
+    nonce: u8,
 }
 
 struct StateTransistionRequest<'a> {
     fund_acc: &'a mut Fund,
-    amount: u64,
 }
@@ -0,0 +1,125 @@
+use crate::access_control;
+use fund::{
+    accounts::fund::Fund,
+    error::{FundError, FundErrorCode},
+};
+use serum_common::pack::Pack;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    msg,
+    pubkey::Pubkey,
+};
+
+pub fn handler(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_max_balance: Option<u64>,
+    new_closes_at: Option<i64>,
+) -> Result<(), FundError> {
+    msg!("handler reopen initiate");
+    let acc_info = &mut accounts.iter();
+
+    let fund_acc_info = next_account_info(acc_info)?;
+    let fund_owner_acc_info = next_account_info(acc_info)?;
+    let clock_acc_info = next_account_info(acc_info)?;
+    let signer_acc_infos = acc_info.as_slice();
+
+    access_control(AccessControlRequest {
+        program_id,
+        fund_acc_info,
+        fund_owner_acc_info,
+        clock_acc_info,
+        signer_acc_infos,
+        new_max_balance,
+        new_closes_at,
+    })?;
+
+    Fund::unpack_mut(
+        &mut fund_acc_info.try_borrow_mut_data()?,
+        &mut |fund_acc: &mut Fund| {
+            state_transition(StateTransistionRequest {
+                fund_acc,
+                new_max_balance,
+                new_closes_at,
+            })
+            .map_err(Into::into)
+        },
+    )?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<(), FundError> {
+    let AccessControlRequest {
+        program_id,
+        fund_acc_info,
+        fund_owner_acc_info,
+        clock_acc_info,
+        signer_acc_infos,
+        new_max_balance,
+        new_closes_at,
+    } = req;
+
+    let _ = access_control::check_owner(
+        program_id,
+        fund_acc_info,
+        fund_owner_acc_info,
+        signer_acc_infos,
+    )?;
+
+    let fund = access_control::fund(fund_acc_info, program_id)?;
+    access_control::not_paused(&fund)?;
+    // a fund past its deposit window is closed even though it was never closed
+    let clock = access_control::clock(clock_acc_info)?;
+    if fund.is_open(clock.unix_timestamp) {
+        return Err(FundErrorCode::FundOpen.into());
+    }
+    // depositors of a reopened fund join the same balance, so it has to be untouched
+    if fund.withdrawn > 0 {
+        return Err(FundErrorCode::FundWithdrawn.into());
+    }
+
+    let max_balance = new_max_balance.unwrap_or(fund.max_balance);
+    if max_balance < fund.max_balance || max_balance <= fund.balance {
+        return Err(FundErrorCode::InvalidMaxBalance.into());
+    }
+
+    let closes_at = new_closes_at.unwrap_or(fund.closes_at);
+    if closes_at != 0 && (closes_at <= clock.unix_timestamp || closes_at <= fund.opens_at) {
+        return Err(FundErrorCode::InvalidDepositWindow.into());
+    }
+
+    msg!("access control reopen success");
+
+    Ok(())
+}
+
+fn state_transition(req: StateTransistionRequest) -> Result<(), FundError> {
+    let StateTransistionRequest {
+        fund_acc,
+        new_max_balance,
+        new_closes_at,
+    } = req;
+
+    fund_acc.reopen(new_max_balance, new_closes_at);
+
+    msg!("state transition reopen success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a, 'b> {
+    program_id: &'a Pubkey,
+    fund_acc_info: &'a AccountInfo<'b>,
+    fund_owner_acc_info: &'a AccountInfo<'b>,
+    clock_acc_info: &'a AccountInfo<'b>,
+    signer_acc_infos: &'a [AccountInfo<'b>],
+    new_max_balance: Option<u64>,
+    new_closes_at: Option<i64>,
+}
+
+struct StateTransistionRequest<'c> {
+    fund_acc: &'c mut Fund,
+    new_max_balance: Option<u64>,
+    new_closes_at: Option<i64>,
+}
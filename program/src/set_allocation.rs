@@ -0,0 +1,166 @@
+use crate::{access_control, pda};
+use fund::{
+    accounts::{allocation::Allocation, Fund},
+    error::{FundError, FundErrorCode},
+};
+use serum_common::pack::Pack;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    msg,
+    pubkey::Pubkey,
+};
+use std::convert::Into;
+
+pub fn handler(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    depositor: Pubkey,
+    min: u64,
+    max: u64,
+) -> Result<(), FundError> {
+    msg!("handler: set_allocation");
+    let acc_infos = &mut accounts.iter();
+
+    let fund_acc_info = next_account_info(acc_infos)?;
+    let owner_acc_info = next_account_info(acc_infos)?;
+    let payer_acc_info = next_account_info(acc_infos)?;
+    let allocation_acc_info = next_account_info(acc_infos)?;
+    let system_program_acc_info = next_account_info(acc_infos)?;
+    let rent_acc_info = next_account_info(acc_infos)?;
+    let signer_acc_infos = acc_infos.as_slice();
+
+    let AccessControlResponse { nonce } = access_control(AccessControlRequest {
+        program_id,
+        fund_acc_info,
+        owner_acc_info,
+        payer_acc_info,
+        allocation_acc_info,
+        signer_acc_infos,
+        depositor: &depositor,
+        min,
+        max,
+    })?;
+
+    // first allocation of the depositor, create the account
+    if let Some(nonce) = nonce {
+        let rent = access_control::rent(rent_acc_info)?;
+        let space = Allocation::default().size()?;
+        let signer_seeds = Allocation::signer_seeds(fund_acc_info.key, &depositor, &nonce);
+        pda::create_account(
+            program_id,
+            payer_acc_info,
+            allocation_acc_info,
+            system_program_acc_info,
+            &rent,
+            space,
+            &signer_seeds,
+        )?;
+    }
+
+    Allocation::unpack_mut(
+        &mut allocation_acc_info.try_borrow_mut_data()?,
+        &mut |allocation_acc: &mut Allocation| {
+            if !allocation_acc.initialized {
+                allocation_acc.initialized = true;
+                allocation_acc.fund = *fund_acc_info.key;
+                allocation_acc.depositor = depositor;
+            }
+            allocation_acc.min = min;
+            allocation_acc.max = max;
+            Ok(())
+        },
+    )?;
+
+    Fund::unpack_mut(
+        &mut fund_acc_info.try_borrow_mut_data()?,
+        &mut |fund_acc: &mut Fund| {
+            state_transition(StateTransistionRequest { fund_acc }).map_err(Into::into)
+        },
+    )?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<AccessControlResponse, FundError> {
+    let AccessControlRequest {
+        program_id,
+        fund_acc_info,
+        owner_acc_info,
+        payer_acc_info,
+        allocation_acc_info,
+        signer_acc_infos,
+        depositor,
+        min,
+        max,
+    } = req;
+
+    // check owner or authority
+    let _ = access_control::check_authority(
+        program_id,
+        fund_acc_info,
+        owner_acc_info,
+        signer_acc_infos,
+    )?;
+    if !payer_acc_info.is_signer {
+        return Err(FundErrorCode::Unauthorized.into());
+    }
+    let fund = access_control::fund(fund_acc_info, program_id)?;
+    access_control::not_paused(&fund)?;
+    if max == 0 || min > max || max > fund.max_balance {
+        return Err(FundErrorCode::InvalidAllocation.into());
+    }
+
+    let nonce = if allocation_acc_info.owner == program_id {
+        let _ = access_control::allocation(
+            allocation_acc_info,
+            fund_acc_info.key,
+            depositor,
+            program_id,
+        )?;
+        None
+    } else {
+        let (allocation, nonce) = Pubkey::find_program_address(
+            &Allocation::seeds(fund_acc_info.key, depositor),
+            program_id,
+        );
+        if allocation != *allocation_acc_info.key {
+            return Err(FundErrorCode::InvalidAllocation.into());
+        }
+        Some(nonce)
+    };
+
+    msg!("access control set_allocation success");
+
+    Ok(AccessControlResponse { nonce })
+}
+
+fn state_transition(req: StateTransistionRequest) -> Result<(), FundError> {
+    let StateTransistionRequest { fund_acc } = req;
+
+    fund_acc.allocations = true;
+
+    msg!("state transition set_allocation success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a, 'b> {
+    program_id: &'a Pubkey,
+    fund_acc_info: &'a AccountInfo<'b>,
+    owner_acc_info: &'a AccountInfo<'b>,
+    payer_acc_info: &'a AccountInfo<'b>,
+    allocation_acc_info: &'a AccountInfo<'b>,
+    signer_acc_infos: &'a [AccountInfo<'b>],
+    depositor: &'a Pubkey,
+    min: u64,
+    max: u64,
+}
+
+struct AccessControlResponse {
+    /// nonce of the allocation to create when the depositor has none yet
+    nonce: Option<u8>,
+}
+
+struct StateTransistionRequest<'a> {
+    fund_acc: &'a mut Fund,
+}
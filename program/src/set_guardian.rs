@@ -0,0 +1,116 @@
+use crate::access_control;
+use fund::{
+    accounts::fund::Fund,
+    error::{FundError, FundErrorCode},
+};
+use serum_common::pack::Pack;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    msg,
+    pubkey::Pubkey,
+};
+
+pub fn handler(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    guardian: Pubkey,
+) -> Result<(), FundError> {
+    msg!("handler: set_guardian");
+    let acc_infos = &mut accounts.iter();
+
+    let fund_acc_info = next_account_info(acc_infos)?;
+    let signer_acc_info = next_account_info(acc_infos)?;
+    let clock_acc_info = next_account_info(acc_infos)?;
+    let signer_acc_infos = acc_infos.as_slice();
+
+    let AccessControlResponse { by_owner } = access_control(AccessControlRequest {
+        program_id,
+        fund_acc_info,
+        signer_acc_info,
+        clock_acc_info,
+        signer_acc_infos,
+    })?;
+
+    Fund::unpack_mut(
+        &mut fund_acc_info.try_borrow_mut_data()?,
+        &mut |fund_acc: &mut Fund| {
+            state_transition(StateTransistionRequest {
+                fund_acc,
+                guardian,
+                by_owner,
+            })
+            .map_err(Into::into)
+        },
+    )?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<AccessControlResponse, FundError> {
+    let AccessControlRequest {
+        program_id,
+        fund_acc_info,
+        signer_acc_info,
+        clock_acc_info,
+        signer_acc_infos,
+    } = req;
+
+    let fund = access_control::fund(fund_acc_info, program_id)?;
+
+    // the guardian hands its role over, the owner only takes a fund back the
+    // guardian kept paused for too long
+    let by_owner = if fund.guardian != Pubkey::default() && fund.guardian == *signer_acc_info.key {
+        if !signer_acc_info.is_signer {
+            return Err(FundErrorCode::Unauthorized.into());
+        }
+        false
+    } else {
+        access_control::check_owner(program_id, fund_acc_info, signer_acc_info, signer_acc_infos)?;
+        let clock = access_control::clock(clock_acc_info)?;
+        if !fund.pause_expired(clock.unix_timestamp) {
+            return Err(FundErrorCode::InvalidGuardian.into());
+        }
+        true
+    };
+
+    msg!("access control set_guardian success");
+
+    Ok(AccessControlResponse { by_owner })
+}
+
+fn state_transition(req: StateTransistionRequest) -> Result<(), FundError> {
+    let StateTransistionRequest {
+        fund_acc,
+        guardian,
+        by_owner,
+    } = req;
+
+    fund_acc.guardian = guardian;
+    if by_owner {
+        fund_acc.unpause();
+        msg!("event: fund unpaused");
+    }
+
+    msg!("state transition set_guardian success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a, 'b> {
+    program_id: &'a Pubkey,
+    fund_acc_info: &'a AccountInfo<'b>,
+    signer_acc_info: &'a AccountInfo<'b>,
+    clock_acc_info: &'a AccountInfo<'b>,
+    signer_acc_infos: &'a [AccountInfo<'b>],
+}
+
+struct AccessControlResponse {
+    /// the owner replaces the guardian of a fund paused for too long
+    by_owner: bool,
+}
+
+struct StateTransistionRequest<'c> {
+    fund_acc: &'c mut Fund,
+    guardian: Pubkey,
+    by_owner: bool,
+}
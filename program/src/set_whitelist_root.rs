@@ -0,0 +1,88 @@
+use crate::access_control;
+use fund::{
+    accounts::fund::{Fund, FundType},
+    error::{FundError, FundErrorCode},
+};
+use serum_common::pack::Pack;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    msg,
+    pubkey::Pubkey,
+};
+
+pub fn handler(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    root: [u8; 32],
+) -> Result<(), FundError> {
+    msg!("handler: set_whitelist_root");
+    let acc_infos = &mut accounts.iter();
+
+    let fund_acc_info = next_account_info(acc_infos)?;
+    let fund_owner_acc_info = next_account_info(acc_infos)?;
+    let signer_acc_infos = acc_infos.as_slice();
+
+    access_control(AccessControlRequest {
+        program_id,
+        fund_acc_info,
+        fund_owner_acc_info,
+        signer_acc_infos,
+    })?;
+
+    Fund::unpack_mut(
+        &mut fund_acc_info.try_borrow_mut_data()?,
+        &mut |fund_acc: &mut Fund| {
+            state_transition(StateTransistionRequest { fund_acc, root }).map_err(Into::into)
+        },
+    )?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<(), FundError> {
+    let AccessControlRequest {
+        program_id,
+        fund_acc_info,
+        fund_owner_acc_info,
+        signer_acc_infos,
+    } = req;
+
+    // check owner or authority
+    let _ = access_control::check_authority(
+        program_id,
+        fund_acc_info,
+        fund_owner_acc_info,
+        signer_acc_infos,
+    )?;
+    let fund = access_control::fund(fund_acc_info, program_id)?;
+    access_control::not_paused(&fund)?;
+    if !matches!(fund.fund_type, FundType::Raise { .. }) {
+        return Err(FundErrorCode::InvalidFund.into());
+    }
+
+    msg!("access control set_whitelist_root success");
+
+    Ok(())
+}
+
+fn state_transition(req: StateTransistionRequest) -> Result<(), FundError> {
+    let StateTransistionRequest { fund_acc, root } = req;
+
+    fund_acc.whitelist_root = root;
+
+    msg!("state transition set_whitelist_root success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a, 'b> {
+    program_id: &'a Pubkey,
+    fund_acc_info: &'a AccountInfo<'b>,
+    fund_owner_acc_info: &'a AccountInfo<'b>,
+    signer_acc_infos: &'a [AccountInfo<'b>],
+}
+
+struct StateTransistionRequest<'c> {
+    fund_acc: &'c mut Fund,
+    root: [u8; 32],
+}
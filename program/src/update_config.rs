@@ -0,0 +1,200 @@
+use crate::access_control;
+use fund::{
+    accounts::{
+        fund::{Fund, FundType},
+        Whitelist,
+    },
+    error::{FundError, FundErrorCode},
+};
+use serum_common::pack::Pack;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    msg,
+    pubkey::Pubkey,
+};
+
+pub fn handler(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    max_balance: Option<u64>,
+    private: Option<bool>,
+    authority: Option<Pubkey>,
+    guardian: Option<Pubkey>,
+    allocations: Option<bool>,
+) -> Result<(), FundError> {
+    msg!("handler: update_config");
+
+    let acc_infos = &mut accounts.iter();
+
+    let fund_acc_info = next_account_info(acc_infos)?;
+    let fund_owner_acc_info = next_account_info(acc_infos)?;
+    // only passed when turning a raise private
+    let whitelist_acc_info = if private == Some(true) {
+        Some(next_account_info(acc_infos)?)
+    } else {
+        None
+    };
+    let signer_acc_infos = acc_infos.as_slice();
+
+    access_control(AccessControlRequest {
+        program_id,
+        fund_acc_info,
+        fund_owner_acc_info,
+        whitelist_acc_info,
+        signer_acc_infos,
+        max_balance,
+        private,
+        guardian,
+        allocations,
+    })?;
+
+    Fund::unpack_mut(
+        &mut fund_acc_info.try_borrow_mut_data()?,
+        &mut |fund_acc: &mut Fund| {
+            state_transistion(StateTransistionRequest {
+                fund_acc,
+                fund_acc_info,
+                whitelist_acc_info,
+                max_balance,
+                private,
+                authority,
+                guardian,
+                allocations,
+            })
+            .map_err(Into::into)
+        },
+    )?;
+
+    Ok(())
+}
+
+fn access_control(req: AccessControlRequest) -> Result<(), FundError> {
+    msg!("access-control: update_config");
+
+    let AccessControlRequest {
+        program_id,
+        fund_acc_info,
+        fund_owner_acc_info,
+        whitelist_acc_info,
+        signer_acc_infos,
+        max_balance,
+        private,
+        guardian,
+        allocations,
+    } = req;
+
+    let _ = access_control::check_owner(
+        program_id,
+        fund_acc_info,
+        fund_owner_acc_info,
+        signer_acc_infos,
+    )?;
+    let fund = access_control::fund(fund_acc_info, program_id)?;
+    access_control::not_paused(&fund)?;
+
+    if let Some(max_balance) = max_balance {
+        if max_balance < fund.balance {
+            return Err(FundErrorCode::InvalidMaxBalance.into());
+        }
+        if let FundType::Campaign { goal, .. } = fund.fund_type {
+            if max_balance < goal {
+                return Err(FundErrorCode::InvalidMaxBalance.into());
+            }
+        }
+    }
+
+    if let Some(private) = private {
+        if !matches!(fund.fund_type, FundType::Raise { .. }) {
+            return Err(FundErrorCode::InvalidFund.into());
+        }
+        if private {
+            let whitelist_acc_info = whitelist_acc_info.ok_or(FundErrorCode::InvalidWhitelist)?;
+            // a raise created public has no whitelist to check depositors against yet
+            if fund.whitelist == Pubkey::default() {
+                if whitelist_acc_info.owner != program_id {
+                    return Err(FundErrorCode::InvalidAccountOwner.into());
+                }
+                let _ = Whitelist::new(whitelist_acc_info.clone())?;
+            } else if *whitelist_acc_info.key != fund.whitelist {
+                return Err(FundErrorCode::InvalidWhitelist.into());
+            }
+        }
+    }
+
+    // a guardian in place can only be replaced by itself
+    if guardian.is_some() && fund.guardian != Pubkey::default() {
+        return Err(FundErrorCode::InvalidGuardian.into());
+    }
+
+    // allocations are only turned on by setting one
+    if allocations == Some(true) && !fund.allocations {
+        return Err(FundErrorCode::InvalidAllocation.into());
+    }
+
+    msg!("access-control: success");
+
+    Ok(())
+}
+
+fn state_transistion(req: StateTransistionRequest) -> Result<(), FundError> {
+    msg!("state-transition: update_config");
+
+    let StateTransistionRequest {
+        fund_acc,
+        fund_acc_info,
+        whitelist_acc_info,
+        max_balance,
+        private,
+        authority,
+        guardian,
+        allocations,
+    } = req;
+
+    if let Some(max_balance) = max_balance {
+        fund_acc.max_balance = max_balance;
+    }
+    if let Some(private) = private {
+        if private && fund_acc.whitelist == Pubkey::default() {
+            let whitelist_acc_info = whitelist_acc_info.unwrap();
+            Whitelist::new(whitelist_acc_info.clone())?.init(fund_acc_info.key)?;
+            fund_acc.whitelist = *whitelist_acc_info.key;
+        }
+        fund_acc.set_private(private);
+    }
+    if let Some(authority) = authority {
+        fund_acc.authority = authority;
+    }
+    if let Some(guardian) = guardian {
+        fund_acc.guardian = guardian;
+    }
+    if let Some(allocations) = allocations {
+        fund_acc.allocations = allocations;
+    }
+
+    msg!("state-transition: success");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a, 'b> {
+    program_id: &'a Pubkey,
+    fund_acc_info: &'a AccountInfo<'b>,
+    fund_owner_acc_info: &'a AccountInfo<'b>,
+    whitelist_acc_info: Option<&'a AccountInfo<'b>>,
+    signer_acc_infos: &'a [AccountInfo<'b>],
+    max_balance: Option<u64>,
+    private: Option<bool>,
+    guardian: Option<Pubkey>,
+    allocations: Option<bool>,
+}
+
+struct StateTransistionRequest<'a, 'b, 'c> {
+    fund_acc: &'c mut Fund,
+    fund_acc_info: &'a AccountInfo<'b>,
+    whitelist_acc_info: Option<&'a AccountInfo<'b>>,
+    max_balance: Option<u64>,
+    private: Option<bool>,
+    authority: Option<Pubkey>,
+    guardian: Option<Pubkey>,
+    allocations: Option<bool>,
+}
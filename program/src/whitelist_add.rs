@@ -20,42 +20,70 @@ pub fn handler(
 
     let fund_acc_info = next_account_info(acc_infos)?;
     let fund_owner_acc_info = next_account_info(acc_infos)?;
-    let whitelist_acc_info = next_account_info(acc_infos)?;
+    let whitelist_acc_infos = access_control::whitelist_pages(acc_infos)?;
+    let signer_acc_infos = acc_infos.as_slice();
 
-    access_control(AccessControlRequest {
+    let pages = access_control(AccessControlRequest {
         program_id,
         fund_acc_info,
         fund_owner_acc_info,
-        whitelist_acc_info,
+        whitelist_acc_infos: &whitelist_acc_infos,
+        signer_acc_infos,
+        entry,
     })?;
 
-    let whitelist = Whitelist::new(whitelist_acc_info.clone())?;
-
-    state_transistion(StateTransistionRequest { whitelist, entry })
+    state_transistion(StateTransistionRequest { pages, entry })
 }
 
-fn access_control(req: AccessControlRequest) -> Result<(), FundError> {
+fn access_control<'a>(req: AccessControlRequest<'_, 'a>) -> Result<Vec<Whitelist<'a>>, FundError> {
     msg!("access-control: whitelist_add");
 
     let AccessControlRequest {
         program_id,
         fund_acc_info,
         fund_owner_acc_info,
-        whitelist_acc_info,
+        whitelist_acc_infos,
+        signer_acc_infos,
+        entry,
     } = req;
 
-    // check owner
-    let _ = access_control::check_owner(program_id, fund_acc_info, fund_owner_acc_info)?;
+    // the default pubkey marks an empty slot
+    if entry == Pubkey::default() {
+        return Err(FundErrorCode::InvalidWhitelistEntry.into());
+    }
+
+    // check owner or authority
+    let _ = access_control::check_authority(
+        program_id,
+        fund_acc_info,
+        fund_owner_acc_info,
+        signer_acc_infos,
+    )?;
     let fund = access_control::fund(fund_acc_info, program_id)?;
-    let _ = access_control::whitelist(whitelist_acc_info.clone(), &fund, program_id)?;
+    access_control::not_paused(&fund)?;
+    let pages =
+        access_control::whitelist(whitelist_acc_infos, fund_acc_info.key, &fund, program_id)?;
 
-    Ok(())
+    Ok(pages)
 }
 
 fn state_transistion(req: StateTransistionRequest) -> Result<(), FundError> {
-    let StateTransistionRequest { whitelist, entry } = req;
-
-    whitelist.push(entry)?.ok_or(FundErrorCode::WhitelistFull)?;
+    let StateTransistionRequest { pages, entry } = req;
+
+    if Whitelist::find(&pages, &entry)?.is_some() {
+        return Err(FundErrorCode::PubKeyAlreadyExists.into());
+    }
+    // first page with room left, a full whitelist is grown with WhitelistResize
+    let mut page = None;
+    for wl in pages.iter() {
+        if wl.count()? < wl.capacity()? {
+            page = Some(wl);
+            break;
+        }
+    }
+    page.ok_or(FundErrorCode::WhitelistFull)?
+        .push(entry)?
+        .ok_or(FundErrorCode::WhitelistFull)?;
 
     msg!("state-transistion: whitelist_add");
 
@@ -66,10 +94,12 @@ struct AccessControlRequest<'a, 'b> {
     program_id: &'a Pubkey,
     fund_acc_info: &'a AccountInfo<'b>,
     fund_owner_acc_info: &'a AccountInfo<'b>,
-    whitelist_acc_info: &'a AccountInfo<'b>,
+    whitelist_acc_infos: &'a [&'a AccountInfo<'b>],
+    signer_acc_infos: &'a [AccountInfo<'b>],
+    entry: Pubkey,
 }
 
 struct StateTransistionRequest<'a> {
-    whitelist: Whitelist<'a>,
+    pages: Vec<Whitelist<'a>>,
     entry: Pubkey,
 }
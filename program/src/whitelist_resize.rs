@@ -0,0 +1,119 @@
+use crate::access_control;
+use fund::{
+    accounts::Whitelist,
+    error::{FundError, FundErrorCode},
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    msg,
+    pubkey::Pubkey,
+};
+
+pub fn handler(program_id: &Pubkey, accounts: &[AccountInfo]) -> Result<(), FundError> {
+    msg!("handler: whitelist_resize");
+
+    let acc_infos = &mut accounts.iter();
+
+    let fund_acc_info = next_account_info(acc_infos)?;
+    let fund_owner_acc_info = next_account_info(acc_infos)?;
+    let last_page_acc_info = next_account_info(acc_infos)?;
+    let new_page_acc_info = next_account_info(acc_infos)?;
+    let signer_acc_infos = acc_infos.as_slice();
+
+    let AccessControlResponse {
+        last_page,
+        new_page,
+    } = access_control(AccessControlRequest {
+        program_id,
+        fund_acc_info,
+        fund_owner_acc_info,
+        last_page_acc_info,
+        new_page_acc_info,
+        signer_acc_infos,
+    })?;
+
+    state_transistion(StateTransistionRequest {
+        fund_acc_info,
+        last_page,
+        new_page,
+    })
+}
+
+fn access_control<'a>(
+    req: AccessControlRequest<'_, 'a>,
+) -> Result<AccessControlResponse<'a>, FundError> {
+    msg!("access-control: whitelist_resize");
+
+    let AccessControlRequest {
+        program_id,
+        fund_acc_info,
+        fund_owner_acc_info,
+        last_page_acc_info,
+        new_page_acc_info,
+        signer_acc_infos,
+    } = req;
+
+    // check owner or authority
+    let _ = access_control::check_authority(
+        program_id,
+        fund_acc_info,
+        fund_owner_acc_info,
+        signer_acc_infos,
+    )?;
+    let fund = access_control::fund(fund_acc_info, program_id)?;
+    access_control::not_paused(&fund)?;
+    if fund.whitelist == Pubkey::default() {
+        return Err(FundErrorCode::InvalidWhitelist.into());
+    }
+
+    let last_page =
+        access_control::whitelist_page(last_page_acc_info, fund_acc_info.key, program_id)?;
+    if last_page.next()? != Pubkey::default() {
+        return Err(FundErrorCode::InvalidWhitelist.into());
+    }
+
+    if new_page_acc_info.owner != program_id {
+        return Err(FundErrorCode::InvalidAccountOwner.into());
+    }
+    let new_page = Whitelist::new(new_page_acc_info.clone())?;
+
+    Ok(AccessControlResponse {
+        last_page,
+        new_page,
+    })
+}
+
+fn state_transistion(req: StateTransistionRequest) -> Result<(), FundError> {
+    let StateTransistionRequest {
+        fund_acc_info,
+        last_page,
+        new_page,
+    } = req;
+
+    new_page.init(fund_acc_info.key)?;
+    last_page.set_next(new_page.acc_info.key)?;
+
+    msg!("state-transistion: whitelist_resize");
+
+    Ok(())
+}
+
+struct AccessControlRequest<'a, 'b> {
+    program_id: &'a Pubkey,
+    fund_acc_info: &'a AccountInfo<'b>,
+    fund_owner_acc_info: &'a AccountInfo<'b>,
+    last_page_acc_info: &'a AccountInfo<'b>,
+    new_page_acc_info: &'a AccountInfo<'b>,
+    signer_acc_infos: &'a [AccountInfo<'b>],
+}
+
+struct AccessControlResponse<'a> {
+    last_page: Whitelist<'a>,
+    new_page: Whitelist<'a>,
+}
+
+struct StateTransistionRequest<'a, 'b> {
+    fund_acc_info: &'a AccountInfo<'b>,
+    last_page: Whitelist<'b>,
+    new_page: Whitelist<'b>,
+}
@@ -1,6 +1,6 @@
 use crate::access_control;
 use fund::{
-    accounts::{vault::TokenVault, Fund},
+    accounts::{vault::TokenVault, Fund, FundType},
     error::{FundError, FundErrorCode},
 };
 use serum_common::pack::Pack;
@@ -25,14 +25,20 @@ pub fn handler(
     let withdraw_acc_info = next_account_info(acc_infos)?;
     let vault_authority_acc_info = next_account_info(acc_infos)?;
     let token_program_acc_info = next_account_info(acc_infos)?;
+    let clock_acc_info = next_account_info(acc_infos)?;
+    let destination_acc_info = next_account_info(acc_infos)?;
+    let signer_acc_infos = acc_infos.as_slice();
 
     access_control(AccessControlRequest {
         program_id,
         amount,
         fund_acc_info,
         withdraw_acc_info,
+        destination_acc_info,
         vault_acc_info,
         vault_authority_acc_info,
+        clock_acc_info,
+        signer_acc_infos,
     })?;
 
     Fund::unpack_mut(
@@ -41,7 +47,7 @@ pub fn handler(
             state_transistion(StateTransistionRequest {
                 fund_acc,
                 fund_acc_info,
-                withdraw_acc_info,
+                destination_acc_info,
                 vault_acc_info,
                 vault_authority_acc_info,
                 token_program_acc_info,
@@ -60,16 +66,22 @@ fn access_control(req: AccessControlRequest) -> Result<(), FundError> {
         amount,
         fund_acc_info,
         withdraw_acc_info,
+        destination_acc_info,
         vault_acc_info,
         vault_authority_acc_info,
+        clock_acc_info,
+        signer_acc_infos,
     } = req;
 
-    if !withdraw_acc_info.is_signer {
-        return Err(FundErrorCode::Unauthorized.into());
-    }
-
     {
-        let fund = access_control::fund(fund_acc_info, program_id)?;
+        let fund = access_control::withdraw(
+            program_id,
+            fund_acc_info,
+            withdraw_acc_info,
+            destination_acc_info,
+            signer_acc_infos,
+        )?;
+        access_control::not_paused(&fund)?;
         let _ = access_control::vault_join(
             vault_acc_info,
             vault_authority_acc_info,
@@ -77,16 +89,26 @@ fn access_control(req: AccessControlRequest) -> Result<(), FundError> {
             program_id,
         )?;
 
-        if fund.open {
+        // funds past their deposit window no longer need to be closed by hand
+        let clock = access_control::clock(clock_acc_info)?;
+        if fund.is_open(clock.unix_timestamp) {
             return Err(FundErrorCode::FundOpen.into());
         }
+        // raise funds are governed by their share holders
+        if fund.fund_type.eq(&FundType::Raise { private: true })
+            || fund.fund_type.eq(&FundType::Raise { private: false })
+        {
+            return Err(FundErrorCode::WithdrawRequiresProposal.into());
+        }
+        // campaigns only pay out once the goal is raised
+        if !fund.goal_reached() {
+            return Err(FundErrorCode::GoalNotReached.into());
+        }
         if amount > fund.balance {
             return Err(FundErrorCode::InsufficientBalance)?;
         }
     }
 
-    let _ = access_control::withdraw(program_id, fund_acc_info, withdraw_acc_info);
-
     msg!("access control withdraw success");
 
     Ok(())
@@ -96,7 +118,7 @@ fn state_transistion(req: StateTransistionRequest) -> Result<(), FundError> {
     let StateTransistionRequest {
         fund_acc,
         fund_acc_info,
-        withdraw_acc_info,
+        destination_acc_info,
         vault_acc_info,
         vault_authority_acc_info,
         token_program_acc_info,
@@ -104,13 +126,13 @@ fn state_transistion(req: StateTransistionRequest) -> Result<(), FundError> {
     } = req;
 
     {
-        fund_acc.deduct(amount);
-        // transfer from program account to owner of fund
+        fund_acc.deduct(amount)?;
+        // transfer from program account to the destination
         msg!("invoking token transfer");
         let withdraw_instruction = instruction::transfer(
             &spl_token::ID,
             vault_acc_info.key,
-            withdraw_acc_info.key,
+            destination_acc_info.key,
             &vault_authority_acc_info.key,
             &[],
             amount,
@@ -122,7 +144,7 @@ fn state_transistion(req: StateTransistionRequest) -> Result<(), FundError> {
             &withdraw_instruction,
             &[
                 vault_acc_info.clone(),
-                withdraw_acc_info.clone(),
+                destination_acc_info.clone(),
                 vault_authority_acc_info.clone(),
                 token_program_acc_info.clone(),
             ],
@@ -140,14 +162,17 @@ struct AccessControlRequest<'a, 'b> {
     amount: u64,
     fund_acc_info: &'a AccountInfo<'b>,
     withdraw_acc_info: &'a AccountInfo<'b>,
+    destination_acc_info: &'a AccountInfo<'b>,
     vault_acc_info: &'a AccountInfo<'b>,
     vault_authority_acc_info: &'a AccountInfo<'b>,
+    clock_acc_info: &'a AccountInfo<'b>,
+    signer_acc_infos: &'a [AccountInfo<'b>],
 }
 
 struct StateTransistionRequest<'a, 'b, 'c> {
     fund_acc: &'c mut Fund,
     fund_acc_info: &'a AccountInfo<'b>,
-    withdraw_acc_info: &'a AccountInfo<'b>,
+    destination_acc_info: &'a AccountInfo<'b>,
     vault_acc_info: &'a AccountInfo<'b>,
     vault_authority_acc_info: &'a AccountInfo<'b>,
     token_program_acc_info: &'a AccountInfo<'b>,